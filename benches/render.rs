@@ -0,0 +1,137 @@
+//! Compare the `LayoutJob` label widget with the painter widget on a 200x60 tank.
+//!
+//! Run with `cargo bench --bench render`. Each frame runs a headless egui pass and
+//! tessellates its output, so layout, galley caching and mesh building are all measured.
+//! Without `--bench` (e.g. under `cargo test --all-targets`) it runs a single frame each.
+
+use std::time::{Duration, Instant};
+
+use asciiquarium_rust::widgets::asciiquarium::{AsciiquariumPalette, FishBehavior};
+use asciiquarium_rust::{
+    update_aquarium, AquariumState, AsciiquariumPainter, AsciiquariumTheme, AsciiquariumWidget,
+    AssetRegistry, FishInstance,
+};
+
+const SIZE: (usize, usize) = (200, 60);
+const FISH: usize = 60;
+const WARMUP_TICKS: usize = 200;
+
+fn main() {
+    let frames = if std::env::args().any(|a| a == "--bench") {
+        300
+    } else {
+        1
+    };
+
+    let registry = AssetRegistry::with_defaults();
+    let mut state = tank(&registry);
+    for _ in 0..WARMUP_TICKS {
+        update_aquarium(&mut state, registry.assets());
+    }
+    let theme = theme();
+
+    println!(
+        "{}x{} tank, {} fish, {} frames",
+        SIZE.0, SIZE.1, FISH, frames
+    );
+    for (name, painter) in [("label (LayoutJob)", false), ("painter", true)] {
+        let per_frame = run(&mut state, &registry, &theme, frames, painter);
+        println!(
+            "{:<18} {:>8.3} ms/frame",
+            name,
+            per_frame.as_secs_f64() * 1000.0
+        );
+    }
+}
+
+fn run(
+    state: &mut AquariumState,
+    registry: &AssetRegistry,
+    theme: &AsciiquariumTheme,
+    frames: usize,
+    painter: bool,
+) -> Duration {
+    let ctx = egui::Context::default();
+    let input = || egui::RawInput {
+        screen_rect: Some(egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(2400.0, 1200.0),
+        )),
+        ..Default::default()
+    };
+    let frame = |state: &mut AquariumState| {
+        update_aquarium(state, registry.assets());
+        let output = ctx.run(input(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                if painter {
+                    ui.add(AsciiquariumPainter {
+                        state,
+                        assets: registry.assets(),
+                        theme,
+                    });
+                } else {
+                    ui.add(AsciiquariumWidget {
+                        state,
+                        assets: registry.assets(),
+                        theme,
+                    });
+                }
+            });
+        });
+        ctx.tessellate(output.shapes, output.pixels_per_point).len()
+    };
+
+    // First frame builds the font atlas; keep it out of the measurement.
+    frame(state);
+    let start = Instant::now();
+    for _ in 0..frames {
+        std::hint::black_box(frame(state));
+    }
+    start.elapsed() / frames as u32
+}
+
+fn tank(registry: &AssetRegistry) -> AquariumState {
+    let mut state = AquariumState {
+        size: SIZE,
+        ..Default::default()
+    };
+    let mut seed = 0x2545_F491_4F6C_DD1Du64;
+    for i in 0..FISH {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let Some(idx) = registry.pick(&[], seed) else {
+            break;
+        };
+        let x = (seed >> 20) as usize % SIZE.0;
+        let y = 5 + (seed >> 40) as usize % (SIZE.1 - 10);
+        let vx = if i % 2 == 0 { 6.0 } else { -6.0 };
+        state.fishes.push(FishInstance {
+            fish_art_index: idx,
+            position: (x as f32, y as f32),
+            velocity: (vx, 0.0),
+        });
+        state.fish_behaviors.push(FishBehavior::Normal);
+    }
+    state
+}
+
+fn theme() -> AsciiquariumTheme {
+    AsciiquariumTheme {
+        text_color: egui::Color32::from_rgb(180, 220, 255),
+        background: Some(egui::Color32::from_rgb(8, 12, 16)),
+        wrap: false,
+        enable_color: true,
+        palette: Some(AsciiquariumPalette {
+            water: egui::Color32::from_rgb(120, 180, 255),
+            water_trail: egui::Color32::from_rgba_unmultiplied(120, 180, 255, 120),
+            seaweed: egui::Color32::from_rgb(60, 180, 120),
+            castle: egui::Color32::from_rgb(200, 200, 200),
+            ship: egui::Color32::from_rgb(230, 230, 230),
+            bubble: egui::Color32::from_rgb(200, 230, 255),
+            shark: egui::Color32::from_rgb(180, 200, 210),
+            whale: egui::Color32::from_rgb(160, 190, 210),
+            fish: egui::Color32::from_rgb(255, 200, 120),
+        }),
+    }
+}
@@ -0,0 +1,34 @@
+//! With the `build-assets` feature, regenerate the creature assets from the original Perl
+//! script into `OUT_DIR/generated_assets.rs` (see `src/extract.rs`). Without it, the
+//! committed `src/widgets/generated_assets.rs` is used and this script does nothing.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+// A build script cannot depend on the crate it builds, so it compiles the library's
+// `extract` module on its own.
+#[path = "src/extract.rs"]
+#[allow(dead_code)]
+mod extract;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    if env::var_os("CARGO_FEATURE_BUILD_ASSETS").is_none() {
+        return;
+    }
+    println!("cargo:rerun-if-changed={}", extract::ORIGINAL_SCRIPT_PATH);
+    println!("cargo:rerun-if-changed=src/extract.rs");
+
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default());
+    let input_path = manifest_dir.join(extract::ORIGINAL_SCRIPT_PATH);
+    // Generating from nothing would build a crate without sprites; fail the build instead.
+    let input = fs::read_to_string(&input_path)
+        .unwrap_or_else(|err| panic!("cannot read {}: {}", input_path.display(), err));
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap_or_default());
+    let out_path = out_dir.join("generated_assets.rs");
+    if let Err(err) = fs::write(&out_path, extract::generate_from_script(&input)) {
+        panic!("cannot write {}: {}", out_path.display(), err);
+    }
+}
@@ -1,12 +1,15 @@
 use std::time::Duration;
 
 use asciiquarium_rust::{
-    get_all_fish_assets, update_aquarium, AquariumState, AsciiquariumTheme, AsciiquariumWidget,
-    FishInstance,
+    apply_layout, capture_layout, fit_aquarium_to_ui, hour_from_unix_seconds, AquariumState,
+    AsciiquariumInteractive, AsciiquariumTheme, AsciiquariumWidget, AssetDrop, AssetRegistry,
+    AssetTag, BubbleEmitter, Current, DayClock, DayNight, Decoration, DecorationAnimation,
+    DecorationKind, Eddy, EmitterAnchor, FishInstance, Seabed, SimClock, TankLayout, WeatherKind,
 };
 use eframe::egui;
 use rand::Rng;
 
+#[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result<()> {
     let native_options = eframe::NativeOptions::default();
     eframe::run_native(
@@ -16,18 +19,47 @@ fn main() -> eframe::Result<()> {
     )
 }
 
+/// Web entry point: runs the demo in the `<canvas id="asciiquarium_canvas">` of the page
+/// that loads it (see `web/index.html`).
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    wasm_bindgen_futures::spawn_local(async {
+        let started = eframe::WebRunner::new()
+            .start(
+                "asciiquarium_canvas",
+                eframe::WebOptions::default(),
+                Box::new(|_cc| Box::new(MyApp::new())),
+            )
+            .await;
+        if let Err(err) = started {
+            web_sys::console::error_1(&err);
+        }
+    });
+}
+
 struct MyApp {
-    assets: Vec<asciiquarium_rust::FishArt>,
+    registry: AssetRegistry,
     state: AquariumState,
     theme: AsciiquariumTheme,
-    // Controls repaint cadence (ms). Simulation uses an internal dt; higher values reduce CPU and may not linearly affect perceived speed.
+    // Controls repaint cadence (ms). The clock runs fixed ticks for the elapsed time, so the
+    // tank keeps its speed; higher values only reduce CPU (and smoothness).
     frame_ms: u64,
+    clock: SimClock,
     bg_enabled: bool,
+    use_painter: bool,
+    auto_fit: bool,
+    // Edit mode pauses the simulation and shows the asset palette.
+    edit_mode: bool,
+    layout_status: String,
+    // Drive the day/night cycle from the system clock (UTC) instead of ticks.
+    wall_clock: bool,
 }
 
+const LAYOUT_PATH: &str = "asciiquarium_layout.txt";
+
 impl MyApp {
     fn new() -> Self {
-        let assets = get_all_fish_assets();
+        let registry = AssetRegistry::with_defaults();
 
         // Choose an initial grid size. You can make this dynamic later if desired.
         let size = (80usize, 24usize);
@@ -40,7 +72,7 @@ impl MyApp {
 
         // Seed with a few random fish
         for _ in 0..6 {
-            spawn_random_fish(&mut state, assets.len());
+            spawn_random_fish(&mut state, &registry, &[]);
         }
 
         let theme = AsciiquariumTheme {
@@ -52,43 +84,61 @@ impl MyApp {
         };
 
         Self {
-            assets,
+            registry,
             state,
             theme,
             frame_ms: 50,
+            clock: SimClock::new(),
             bg_enabled: true,
+            use_painter: true,
+            auto_fit: true,
+            edit_mode: false,
+            layout_status: String::new(),
+            wall_clock: false,
         }
     }
 }
 
 impl eframe::App for MyApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Drive animation based on a simple frame duration.
-        update_aquarium(&mut self.state, &self.assets);
+        // Advance by the time egui measured since the last frame (paused while editing).
+        let elapsed = ctx.input(|i| i.unstable_dt);
+        if !self.edit_mode {
+            self.clock
+                .advance(&mut self.state, self.registry.assets(), elapsed);
+        }
         ctx.request_repaint_after(Duration::from_millis(self.frame_ms));
+        if let (true, Some(cycle)) = (self.wall_clock, self.state.env.day_night.as_mut()) {
+            cycle.clock = DayClock::Hour(hour_from_unix_seconds(unix_seconds(), 0));
+        }
 
         egui::TopBottomPanel::top("top_controls").show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.label("Grid:");
-                // Keep grid integers reasonable. Avoid sliders for usize to reduce friction.
-                if ui.button("-W").clicked() && self.state.size.0 > 10 {
-                    self.state.size.0 -= 2;
-                }
-                if ui.button("+W").clicked() {
-                    self.state.size.0 += 2;
-                }
-                if ui.button("-H").clicked() && self.state.size.1 > 5 {
-                    self.state.size.1 -= 1;
-                }
-                if ui.button("+H").clicked() {
-                    self.state.size.1 += 1;
+                ui.checkbox(&mut self.auto_fit, "Auto-fit");
+                if self.auto_fit {
+                    ui.label(format!("{}x{}", self.state.size.0, self.state.size.1));
+                } else {
+                    // Keep grid integers reasonable. Avoid sliders for usize to reduce friction.
+                    if ui.button("-W").clicked() && self.state.size.0 > 10 {
+                        self.state.size.0 -= 2;
+                    }
+                    if ui.button("+W").clicked() {
+                        self.state.size.0 += 2;
+                    }
+                    if ui.button("-H").clicked() && self.state.size.1 > 5 {
+                        self.state.size.1 -= 1;
+                    }
+                    if ui.button("+H").clicked() {
+                        self.state.size.1 += 1;
+                    }
                 }
 
                 ui.separator();
 
                 ui.label("Frame (ms):");
                 ui.label(format!("{} ms", self.frame_ms));
-                ui.small("Render cadence; simulation uses fixed dt ~33ms");
+                ui.small("Render cadence; the simulation keeps real time");
                 if ui.button("-").clicked() && self.frame_ms > 5 {
                     self.frame_ms -= 2;
                 }
@@ -113,6 +163,8 @@ impl eframe::App for MyApp {
                     self.theme.background = None;
                 }
 
+                ui.checkbox(&mut self.use_painter, "Painter (interactive)");
+
                 // Colorized rendering toggle and palette controls
                 ui.checkbox(&mut self.theme.enable_color, "Color");
                 if self.theme.enable_color {
@@ -166,35 +218,227 @@ impl eframe::App for MyApp {
                 ui.separator();
 
                 if ui.button("Add fish").clicked() {
-                    spawn_random_fish(&mut self.state, self.assets.len());
+                    spawn_random_fish(&mut self.state, &self.registry, &[]);
+                }
+                if ui.button("Add small fish").clicked() {
+                    spawn_random_fish(&mut self.state, &self.registry, &[AssetTag::Small]);
+                }
+                ui.separator();
+
+                let mut seabed = self.state.env.seabed.is_some();
+                if ui.checkbox(&mut seabed, "Seabed").changed() {
+                    self.state.env.seabed = seabed.then(|| Seabed::new(rand::random()));
+                }
+                let weather = &mut self.state.env.weather.kind;
+                egui::ComboBox::from_id_source("weather")
+                    .selected_text(weather.name())
+                    .show_ui(ui, |ui| {
+                        for kind in [
+                            WeatherKind::Calm,
+                            WeatherKind::Fair,
+                            WeatherKind::Rain,
+                            WeatherKind::Storm,
+                        ] {
+                            ui.selectable_value(weather, kind, kind.name());
+                        }
+                    });
+                let label = match &self.state.env.current {
+                    None => "Still",
+                    Some(Current::Uniform(_)) => "Drift",
+                    Some(Current::Layered { .. }) => "Surface drift",
+                    Some(Current::Eddies(_)) => "Eddies",
+                };
+                egui::ComboBox::from_id_source("current")
+                    .selected_text(label)
+                    .show_ui(ui, |ui| {
+                        for name in ["Still", "Drift", "Surface drift", "Eddies"] {
+                            if ui.selectable_label(label == name, name).clicked() {
+                                self.state.env.current = current_preset(name, self.state.size);
+                            }
+                        }
+                    });
+                ui.checkbox(&mut self.state.env.merge_bubbles, "Merge bubbles");
+                let mut day_night = self.state.env.day_night.is_some();
+                if ui.checkbox(&mut day_night, "Day/night").changed() {
+                    self.state.env.day_night = day_night.then(DayNight::default);
                 }
+                if day_night
+                    && ui.checkbox(&mut self.wall_clock, "Wall clock").changed()
+                    && !self.wall_clock
+                {
+                    self.state.env.day_night = Some(DayNight::default());
+                }
+                ui.checkbox(&mut self.edit_mode, "Edit");
+                if ui.button("Save layout").clicked() {
+                    let text = capture_layout(&self.state, &self.registry).to_text();
+                    self.layout_status = match save_layout_text(&text) {
+                        Ok(()) => format!("Saved {}", LAYOUT_PATH),
+                        Err(e) => format!("Save failed: {}", e),
+                    };
+                }
+                if ui.button("Load layout").clicked() {
+                    self.layout_status = match load_layout_text()
+                        .and_then(|t| TankLayout::from_text(&t).map_err(|e| e.to_string()))
+                    {
+                        Ok(layout) => {
+                            let missing = apply_layout(&mut self.state, &self.registry, &layout);
+                            format!("Loaded {} ({} unknown fish)", LAYOUT_PATH, missing.len())
+                        }
+                        Err(e) => format!("Load failed: {}", e),
+                    };
+                }
+                ui.label(&self.layout_status);
+
                 if ui.button("Reset").clicked() {
                     self.state.fishes.clear();
+                    self.state.fish_behaviors.clear();
+                    self.state.fish_cruises.clear();
                     for _ in 0..6 {
-                        spawn_random_fish(&mut self.state, self.assets.len());
+                        spawn_random_fish(&mut self.state, &self.registry, &[]);
                     }
                 }
             });
         });
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            // Render widget as a single monospace label
-            ui.add(AsciiquariumWidget {
-                state: &self.state,
-                assets: &self.assets,
-                theme: &self.theme,
+        if self.edit_mode {
+            egui::SidePanel::left("palette").show(ctx, |ui| {
+                ui.label(
+                    "Drag a fish into the tank. Drag to move, right-click or Delete to remove.",
+                );
+                ui.horizontal_wrapped(|ui| {
+                    let x = self.state.size.0 / 3;
+                    let decorations = [
+                        ("Chest", Decoration::treasure_chest(x)),
+                        ("Rocks", Decoration::new(DecorationKind::Rocks, x)),
+                        (
+                            "Sunken ship",
+                            Decoration::new(DecorationKind::SunkenShip, x),
+                        ),
+                        (
+                            "Diver helmet",
+                            Decoration::new(DecorationKind::DiverHelmet, x).with_animation(Some(
+                                DecorationAnimation {
+                                    period: 90,
+                                    bubbles: 2,
+                                },
+                            )),
+                        ),
+                    ];
+                    for (label, decoration) in decorations {
+                        if ui.button(label).clicked() {
+                            self.state.env.decorations.push(decoration);
+                        }
+                    }
+                    if ui.button("Aerator").clicked() {
+                        // A stone that bubbles in bursts of four every three seconds.
+                        let anchor = EmitterAnchor::Decoration(self.state.env.decorations.len());
+                        self.state
+                            .env
+                            .decorations
+                            .push(Decoration::new(DecorationKind::AeratorStone, x));
+                        self.state
+                            .env
+                            .emitters
+                            .push(BubbleEmitter::new(anchor).with_period(90).with_burst(4, 10));
+                    }
+                });
+                ui.separator();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for (idx, entry) in self.registry.entries().iter().enumerate() {
+                        let id = egui::Id::new(("palette", idx));
+                        ui.dnd_drag_source(id, AssetDrop(idx), |ui| {
+                            ui.monospace(&entry.id);
+                        });
+                    }
+                });
             });
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            if self.auto_fit {
+                // Match the grid to the panel; out-of-bounds entities are moved back in.
+                fit_aquarium_to_ui(ui, &mut self.state, self.registry.assets());
+            }
+            if self.use_painter || self.edit_mode {
+                // Paint the cell grid directly (faster for large tanks), with tooltips and
+                // click-to-scare.
+                AsciiquariumInteractive {
+                    state: &mut self.state,
+                    assets: self.registry.assets(),
+                    theme: &self.theme,
+                    registry: Some(&self.registry),
+                    tooltips: true,
+                    click_to_scare: true,
+                    edit_mode: self.edit_mode,
+                }
+                .show(ui);
+            } else {
+                // Render widget as a single monospace label
+                ui.add(AsciiquariumWidget {
+                    state: &self.state,
+                    assets: self.registry.assets(),
+                    theme: &self.theme,
+                });
+            }
         });
     }
 }
 
-fn spawn_random_fish(state: &mut AquariumState, asset_count: usize) {
-    if asset_count == 0 {
-        return;
-    }
+/// Seconds since the Unix epoch, for the wall-clock day/night cycle.
+#[cfg(not(target_arch = "wasm32"))]
+fn unix_seconds() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Seconds since the Unix epoch from the browser (`SystemTime` is unavailable on the web).
+#[cfg(target_arch = "wasm32")]
+fn unix_seconds() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
+
+/// Save a layout to `LAYOUT_PATH` in the working directory.
+#[cfg(not(target_arch = "wasm32"))]
+fn save_layout_text(text: &str) -> Result<(), String> {
+    std::fs::write(LAYOUT_PATH, text).map_err(|e| e.to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_layout_text() -> Result<String, String> {
+    std::fs::read_to_string(LAYOUT_PATH).map_err(|e| e.to_string())
+}
+
+/// The page's `localStorage`, where the web build keeps its layout under `LAYOUT_PATH`.
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Result<web_sys::Storage, String> {
+    web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .ok_or_else(|| "no localStorage".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn save_layout_text(text: &str) -> Result<(), String> {
+    local_storage()?
+        .set_item(LAYOUT_PATH, text)
+        .map_err(|_| "localStorage refused the layout".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn load_layout_text() -> Result<String, String> {
+    local_storage()?
+        .get_item(LAYOUT_PATH)
+        .ok()
+        .flatten()
+        .ok_or_else(|| "nothing saved yet".to_string())
+}
+
+fn spawn_random_fish(state: &mut AquariumState, registry: &AssetRegistry, tags: &[AssetTag]) {
     let mut rng = rand::thread_rng();
 
-    let idx = rng.gen_range(0..asset_count);
+    let Some(idx) = registry.pick(tags, rng.gen()) else {
+        return;
+    };
 
     // Random position within grid; update() will clamp on edges using asset size
     let max_x = if state.size.0 > 0 {
@@ -228,3 +472,28 @@ fn spawn_random_fish(state: &mut AquariumState, asset_count: usize) {
         .fish_behaviors
         .push(asciiquarium_rust::widgets::asciiquarium::FishBehavior::Normal);
 }
+
+/// Currents offered by the demo's "current" menu.
+fn current_preset(name: &str, size: (usize, usize)) -> Option<Current> {
+    let (w, h) = (size.0 as f32, size.1 as f32);
+    match name {
+        "Drift" => Some(Current::Uniform((3.0, 0.0))),
+        "Surface drift" => Some(Current::Layered {
+            surface: (5.0, 0.0),
+            bottom: (0.0, 0.0),
+        }),
+        "Eddies" => Some(Current::Eddies(vec![
+            Eddy {
+                center: (w * 0.3, h * 0.5),
+                radius: h * 0.6,
+                strength: 3.0,
+            },
+            Eddy {
+                center: (w * 0.7, h * 0.5),
+                radius: h * 0.6,
+                strength: -3.0,
+            },
+        ])),
+        _ => None,
+    }
+}
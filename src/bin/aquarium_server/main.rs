@@ -0,0 +1,419 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use asciiquarium_rust::widgets::asciiquarium::{AsciiquariumPalette, CLASSIC_DT};
+use asciiquarium_rust::widgets::stream::error_message;
+use asciiquarium_rust::{
+    add_fish_at, apply_control, apply_layout, stream_frame, update_aquarium, AquariumState,
+    AsciiquariumTheme, AssetRegistry, ControlMessage, FrameStream, TankLayout,
+};
+use egui::Color32;
+
+/// Streams one shared aquarium to browsers on the local network.
+///
+/// Usage:
+///   cargo run --features server --bin aquarium_server [ADDR] [LAYOUT]
+///
+/// ADDR defaults to 127.0.0.1:8080; LAYOUT is a saved tank layout to start from (a 100x30
+/// tank with seeded fish without one). Open `http://ADDR/` on every screen.
+///
+/// Routes (see `widgets::stream` for the messages):
+///   GET  /         the bundled viewer page
+///   GET  /events   Server-Sent Events, one `data:` line of frame/diff JSON per message
+///   POST /control  a control message as the body; 204, or 400 with an error message
+///
+/// The simulation runs once, on its own thread at the classic tick rate, however many
+/// viewers are connected; each viewer gets a full frame first and row diffs after.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let addr = args.next().unwrap_or_else(|| "127.0.0.1:8080".to_string());
+    let registry = AssetRegistry::with_defaults();
+    let mut state = AquariumState {
+        size: (100, 30),
+        ..Default::default()
+    };
+    match args.next() {
+        Some(path) => {
+            let layout = TankLayout::from_text(&std::fs::read_to_string(path)?)?;
+            for id in apply_layout(&mut state, &registry, &layout) {
+                eprintln!("unknown asset in layout: {id}");
+            }
+        }
+        None => seed_fish(&mut state, &registry, 14),
+    }
+
+    let listener = TcpListener::bind(&addr)?;
+    println!("aquarium on http://{}/", listener.local_addr()?);
+    serve(listener, Arc::new(Server::new(state, registry, theme())));
+    Ok(())
+}
+
+/// The viewer page served at `/`.
+const VIEWER: &str = include_str!("viewer.html");
+/// Largest control message body accepted.
+const MAX_BODY: usize = 4096;
+/// Longest request or header line accepted, in bytes.
+const MAX_LINE: usize = 8192;
+/// Most header lines accepted in a request.
+const MAX_HEADERS: usize = 64;
+/// Messages queued for a viewer (about two seconds of frames); a viewer this far behind is
+/// dropped, and its browser reconnects for a fresh frame.
+const VIEWER_QUEUE: usize = 64;
+/// Time between SSE comments sent to notice viewers that went away while nothing changed.
+const KEEPALIVE: Duration = Duration::from_secs(15);
+
+struct Tank {
+    state: AquariumState,
+    paused: bool,
+}
+
+/// A connected `/events` stream. Its own thread does the writing, so a stalled screen holds
+/// up nobody else.
+struct Viewer {
+    outbox: SyncSender<String>,
+    frames: FrameStream,
+}
+
+impl Viewer {
+    /// Start the writer thread for `stream`.
+    fn new(mut stream: TcpStream) -> Self {
+        let (outbox, messages) = mpsc::sync_channel::<String>(VIEWER_QUEUE);
+        thread::spawn(move || {
+            for message in messages {
+                if stream.write_all(message.as_bytes()).is_err() {
+                    return;
+                }
+            }
+        });
+        Self {
+            outbox,
+            frames: FrameStream::new(),
+        }
+    }
+
+    /// Queue `message`; false if the viewer is gone or too far behind.
+    fn send(&self, message: String) -> bool {
+        self.outbox.try_send(message).is_ok()
+    }
+}
+
+struct Server {
+    registry: AssetRegistry,
+    theme: AsciiquariumTheme,
+    tank: Mutex<Tank>,
+    viewers: Mutex<Vec<Viewer>>,
+}
+
+impl Server {
+    fn new(state: AquariumState, registry: AssetRegistry, theme: AsciiquariumTheme) -> Self {
+        Self {
+            registry,
+            theme,
+            tank: Mutex::new(Tank {
+                state,
+                paused: false,
+            }),
+            viewers: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+fn theme() -> AsciiquariumTheme {
+    AsciiquariumTheme {
+        background: Some(Color32::from_rgb(8, 16, 36)),
+        text_color: Color32::from_rgb(230, 230, 230),
+        enable_color: true,
+        palette: Some(AsciiquariumPalette {
+            water: Color32::from_rgb(120, 180, 255),
+            water_trail: Color32::from_rgb(120, 180, 255),
+            seaweed: Color32::from_rgb(60, 180, 120),
+            castle: Color32::from_rgb(200, 200, 200),
+            ship: Color32::from_rgb(230, 230, 230),
+            bubble: Color32::from_rgb(200, 230, 255),
+            shark: Color32::from_rgb(180, 200, 210),
+            whale: Color32::from_rgb(160, 190, 210),
+            fish: Color32::from_rgb(255, 200, 120),
+        }),
+        ..Default::default()
+    }
+}
+
+/// Add `count` fish at seeded cells, so every run starts the same.
+fn seed_fish(state: &mut AquariumState, registry: &AssetRegistry, count: u64) {
+    let (w, h) = state.size;
+    for i in 0..count {
+        let seed = (i + 1)
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let Some(index) = registry.pick(&[], seed >> 16) else {
+            return;
+        };
+        let cell = (
+            (seed >> 24) as usize % w.max(1),
+            4 + (seed >> 40) as usize % h.saturating_sub(6).max(1),
+        );
+        state.tick = i;
+        add_fish_at(state, registry.assets(), index, cell);
+    }
+    state.tick = 0;
+}
+
+/// Run the clock and answer connections on `listener` until it fails.
+fn serve(listener: TcpListener, server: Arc<Server>) {
+    let clock = Arc::clone(&server);
+    thread::spawn(move || run_clock(&clock));
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let server = Arc::clone(&server);
+        thread::spawn(move || {
+            if let Err(err) = handle(stream, &server) {
+                eprintln!("connection: {err}");
+            }
+        });
+    }
+}
+
+/// Tick the simulation and queue each viewer its next message; viewers that cannot keep up
+/// or went away are dropped.
+fn run_clock(server: &Server) {
+    // Wall time, not ticks: the tick stands still while the tank is paused.
+    let mut last_keepalive = Instant::now();
+    loop {
+        thread::sleep(Duration::from_secs_f32(CLASSIC_DT));
+        let frame = {
+            let mut tank = server.tank.lock().unwrap_or_else(PoisonError::into_inner);
+            if !tank.paused {
+                update_aquarium(&mut tank.state, server.registry.assets());
+            }
+            stream_frame(
+                &tank.state,
+                server.registry.assets(),
+                &server.theme,
+                tank.paused,
+            )
+        };
+        let keepalive = last_keepalive.elapsed() >= KEEPALIVE;
+        if keepalive {
+            last_keepalive = Instant::now();
+        }
+        // Queueing never blocks, so no viewer's socket is touched under the lock.
+        let mut viewers = server
+            .viewers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        viewers.retain_mut(|viewer| match viewer.frames.next_message(&frame) {
+            Some(message) => viewer.send(format!("data: {message}\n\n")),
+            None if keepalive => viewer.send(":\n\n".to_string()),
+            None => true,
+        });
+    }
+}
+
+fn handle(mut stream: TcpStream, server: &Server) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(1)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let too_large = |stream: &mut TcpStream| {
+        respond(
+            stream,
+            "431 Request Header Fields Too Large",
+            "text/plain",
+            "too large",
+        )
+    };
+    let Some(request_line) = read_line(&mut reader)? else {
+        return too_large(&mut stream);
+    };
+    // `None` if the header is there but is not a number.
+    let mut content_length = Some(0);
+    for count in 0.. {
+        let Some(header) = read_line(&mut reader)? else {
+            return too_large(&mut stream);
+        };
+        if header.trim().is_empty() {
+            break;
+        }
+        if count == MAX_HEADERS {
+            return too_large(&mut stream);
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().ok();
+            }
+        }
+    }
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    match (method, path) {
+        ("GET", "/" | "/index.html") => respond(&mut stream, "200 OK", "text/html", VIEWER),
+        ("GET", "/events") => {
+            stream.write_all(
+                b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\n\
+                  Cache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n",
+            )?;
+            // The viewer's writer thread takes over, starting with a full frame.
+            let viewer = Viewer::new(stream);
+            server
+                .viewers
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(viewer);
+            Ok(())
+        }
+        ("POST", "/control") if content_length.is_none() => respond(
+            &mut stream,
+            "400 Bad Request",
+            "text/plain",
+            "bad Content-Length",
+        ),
+        ("POST", "/control") if content_length > Some(MAX_BODY) => respond(
+            &mut stream,
+            "413 Payload Too Large",
+            "text/plain",
+            "too large",
+        ),
+        ("POST", "/control") => {
+            let mut body = vec![0; content_length.unwrap_or(0)];
+            reader.read_exact(&mut body)?;
+            let result =
+                ControlMessage::from_json(&String::from_utf8_lossy(&body)).and_then(|message| {
+                    let mut tank = server.tank.lock().unwrap_or_else(PoisonError::into_inner);
+                    let Tank { state, paused } = &mut *tank;
+                    apply_control(state, paused, &server.registry, &message)
+                });
+            match result {
+                Ok(()) => respond(&mut stream, "204 No Content", "text/plain", ""),
+                Err(err) => respond(
+                    &mut stream,
+                    "400 Bad Request",
+                    "application/json",
+                    &error_message(&err),
+                ),
+            }
+        }
+        _ => respond(&mut stream, "404 Not Found", "text/plain", "not found"),
+    }
+}
+
+/// One line of the request head (empty at the end of input); `None` if it is longer than
+/// `MAX_LINE`.
+fn read_line(reader: &mut BufReader<TcpStream>) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    reader.by_ref().take(MAX_LINE as u64).read_line(&mut line)?;
+    Ok((line.len() < MAX_LINE || line.ends_with('\n')).then_some(line))
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}; charset=utf-8\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+
+    /// One request on a fresh connection; the whole response.
+    fn request(addr: SocketAddr, text: &str) -> io::Result<String> {
+        let mut stream = TcpStream::connect(addr)?;
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        stream.write_all(text.as_bytes())?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        Ok(response)
+    }
+
+    fn post(addr: SocketAddr, body: &str) -> io::Result<String> {
+        request(
+            addr,
+            &format!(
+                "POST /control HTTP/1.1\r\nHost: local\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            ),
+        )
+    }
+
+    /// The next SSE `data:` payload, skipping keepalive comments.
+    fn next_event(events: &mut BufReader<TcpStream>) -> io::Result<String> {
+        let mut data = String::new();
+        loop {
+            let mut line = String::new();
+            if events.read_line(&mut line)? == 0 {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+            match line.trim_end().strip_prefix("data: ") {
+                Some(payload) => data.push_str(payload),
+                None if line.trim_end().is_empty() && !data.is_empty() => return Ok(data),
+                None => {}
+            }
+        }
+    }
+
+    #[test]
+    fn streams_frames_and_takes_controls() -> io::Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let state = AquariumState {
+            size: (40, 12),
+            ..Default::default()
+        };
+        let server = Arc::new(Server::new(state, AssetRegistry::with_defaults(), theme()));
+        let shared = Arc::clone(&server);
+        thread::spawn(move || serve(listener, shared));
+
+        let page = request(addr, "GET / HTTP/1.1\r\nHost: local\r\n\r\n")?;
+        assert!(page.starts_with("HTTP/1.1 200 OK") && page.contains("new EventSource"));
+
+        let mut events = TcpStream::connect(addr)?;
+        events.set_read_timeout(Some(Duration::from_secs(5)))?;
+        events.write_all(b"GET /events HTTP/1.1\r\nHost: local\r\n\r\n")?;
+        let mut events = BufReader::new(events);
+        let mut status = String::new();
+        events.read_line(&mut status)?;
+        assert!(status.starts_with("HTTP/1.1 200 OK"));
+        let first = next_event(&mut events)?;
+        assert!(first.starts_with("{\"type\":\"frame\",") && first.contains("\"width\":40,"));
+
+        assert!(post(addr, "{\"type\":\"pause\"}")?.starts_with("HTTP/1.1 204"));
+        assert!(post(addr, "{\"type\":\"feed\",\"x\":20}")?.starts_with("HTTP/1.1 204"));
+        let mut paused = false;
+        for _ in 0..100 {
+            let event = next_event(&mut events)?;
+            assert!(event.starts_with("{\"type\":\"diff\""));
+            if event.contains("\"paused\":true") {
+                paused = true;
+                break;
+            }
+        }
+        assert!(paused);
+        {
+            let tank = server.tank.lock().unwrap_or_else(PoisonError::into_inner);
+            assert!(tank.paused && !tank.state.env.food.is_empty());
+        }
+
+        let error = post(addr, "{\"type\":\"dance\"}")?;
+        assert!(error.starts_with("HTTP/1.1 400"));
+        assert!(error.ends_with("{\"type\":\"error\",\"message\":\"unknown type `dance`\"}"));
+
+        let bad_length = "POST /control HTTP/1.1\r\nContent-Length: 12x\r\n\r\n";
+        let response = request(addr, bad_length)?;
+        assert!(response.starts_with("HTTP/1.1 400") && response.ends_with("bad Content-Length"));
+
+        // Over-long lines and too many headers are turned away before the request is read.
+        let long = format!("GET /{}", "a".repeat(MAX_LINE - 5));
+        assert!(request(addr, &long)?.starts_with("HTTP/1.1 431"));
+        let many = format!("GET / HTTP/1.1\r\n{}", "X-A: b\r\n".repeat(MAX_HEADERS + 1));
+        assert!(request(addr, &many)?.starts_with("HTTP/1.1 431"));
+        Ok(())
+    }
+}
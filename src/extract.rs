@@ -0,0 +1,836 @@
+/*!
+Asset extraction from the original Perl Asciiquarium script.
+
+Agent Log:
+- Moved the parser and code generator out of `src/bin/extract_fish.rs` into this library module.
+- A `#[doc(hidden)]` library module used by the `extract_fish` binary, and included by `build.rs`
+  (via `#[path]`) when the `build-assets` feature regenerates assets into `OUT_DIR`.
+- Kept std-only so the build script can include it without the crate's dependencies.
+- A test checks that the committed `src/widgets/generated_assets.rs` matches a fresh extraction.
+
+Strategy:
+- Locate each `sub <name>` in the Perl script and, inside it, the `my @var = (...)` or
+  `my $var = q{...}` assignment holding the art.
+- Parse the Perl list literal: q{...} (nested braces allowed), q#...#, "..." and nested
+  [ ... ] groups (animation frames), applying Perl's unescaping rules.
+- Fish arrays alternate [art, color_mask, art, color_mask, ...].
+- Creature arrays hold one entry per direction (right-moving first), each entry either a
+  single image or a group of animation frames; masks follow the same layout.
+- Seaweed is built procedurally in the original, so only its two stalk segments are taken.
+- The whale's spout is composited onto its frames at `$spout_align` columns; both values are
+  taken (the `if($dir)` branch is the left-moving one) so the spout anchor comes with the art.
+*/
+
+/// Path of the original script, relative to the crate root.
+pub const ORIGINAL_SCRIPT_PATH: &str = "archive/original/asciiquarium";
+
+/// Path of the committed generated module, relative to the crate root.
+pub const GENERATED_MODULE_PATH: &str = "src/widgets/generated_assets.rs";
+
+/// Extract everything from `input` and render the generated module source.
+pub fn generate_from_script(input: &str) -> String {
+    generate_rust_module(&extract_all(input))
+}
+
+/// How the entries of an image array map onto generated sprites.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SpriteLayout {
+    /// One entry per direction: right-moving first, then left-moving.
+    Directional,
+    /// Every entry is a frame of one animation.
+    Frames,
+    /// A single scalar image.
+    Single,
+}
+
+/// Where to find one creature's art in the original script.
+pub struct SpriteSpec {
+    pub name: &'static str,
+    pub sub_name: &'static str,
+    pub image_var: &'static str,
+    pub mask_var: Option<&'static str>,
+    pub layout: SpriteLayout,
+}
+
+pub const SPRITE_SPECS: &[SpriteSpec] = &[
+    SpriteSpec {
+        name: "SHARK",
+        sub_name: "add_shark",
+        image_var: "shark_image",
+        mask_var: Some("shark_mask"),
+        layout: SpriteLayout::Directional,
+    },
+    SpriteSpec {
+        name: "WHALE",
+        sub_name: "add_whale",
+        image_var: "whale_image",
+        mask_var: Some("whale_mask"),
+        layout: SpriteLayout::Directional,
+    },
+    SpriteSpec {
+        name: "WATER_SPOUT",
+        sub_name: "add_whale",
+        image_var: "water_spout",
+        mask_var: None,
+        layout: SpriteLayout::Frames,
+    },
+    SpriteSpec {
+        name: "SHIP",
+        sub_name: "add_ship",
+        image_var: "ship_image",
+        mask_var: Some("ship_mask"),
+        layout: SpriteLayout::Directional,
+    },
+    SpriteSpec {
+        name: "NEW_MONSTER",
+        sub_name: "add_new_monster",
+        image_var: "monster_image",
+        mask_var: Some("monster_mask"),
+        layout: SpriteLayout::Directional,
+    },
+    SpriteSpec {
+        name: "OLD_MONSTER",
+        sub_name: "add_old_monster",
+        image_var: "monster_image",
+        mask_var: Some("monster_mask"),
+        layout: SpriteLayout::Directional,
+    },
+    SpriteSpec {
+        name: "BIG_FISH_1",
+        sub_name: "add_big_fish_1",
+        image_var: "big_fish_image",
+        mask_var: Some("big_fish_mask"),
+        layout: SpriteLayout::Directional,
+    },
+    SpriteSpec {
+        name: "BIG_FISH_2",
+        sub_name: "add_big_fish_2",
+        image_var: "big_fish_image",
+        mask_var: Some("big_fish_mask"),
+        layout: SpriteLayout::Directional,
+    },
+    SpriteSpec {
+        name: "SPLAT",
+        sub_name: "add_splat",
+        image_var: "splat_image",
+        mask_var: None,
+        layout: SpriteLayout::Frames,
+    },
+    SpriteSpec {
+        name: "CASTLE",
+        sub_name: "add_castle",
+        image_var: "castle_image",
+        mask_var: Some("castle_mask"),
+        layout: SpriteLayout::Single,
+    },
+];
+
+/// Term::Animation transparency rule of the entity drawing a sprite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpriteTransparency {
+    /// `auto_trans => 1`: leading whitespace and `?` are transparent.
+    Auto,
+    /// No option: only `?` is transparent.
+    Opaque,
+    /// `transparent => ' '`: every space is transparent.
+    Spaces,
+}
+
+impl SpriteTransparency {
+    /// Name of the matching `Transparency` variant in the generated module.
+    pub fn rust_name(self) -> &'static str {
+        match self {
+            SpriteTransparency::Auto => "Auto",
+            SpriteTransparency::Opaque => "Opaque",
+            SpriteTransparency::Spaces => "Spaces",
+        }
+    }
+}
+
+/// One generated sprite: animation frames plus colour masks (possibly empty).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sprite {
+    pub name: String,
+    pub frames: Vec<String>,
+    pub masks: Vec<String>,
+    pub transparency: SpriteTransparency,
+}
+
+/// A fish art block with its colour mask.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FishBlock {
+    pub art: String,
+    pub mask: Option<String>,
+}
+
+/// Everything extracted from the original script.
+#[derive(Debug, Default)]
+pub struct Extracted {
+    pub fish: Vec<FishBlock>,
+    pub sprites: Vec<Sprite>,
+    pub water_lines: Vec<String>,
+    pub seaweed_segments: Vec<String>,
+    /// Columns from the whale's left edge to the spout frames: (right-moving, left-moving).
+    pub whale_spout_align: (usize, usize),
+}
+
+pub fn extract_all(input: &str) -> Extracted {
+    let mut out = Extracted::default();
+
+    for func in ["add_new_fish", "add_old_fish"] {
+        out.fish
+            .extend(extract_fish_blocks_from_function(input, func));
+    }
+
+    for spec in SPRITE_SPECS {
+        out.sprites.extend(extract_sprites(input, spec));
+    }
+
+    if let Some(items) = sub_body(input, "add_environment")
+        .and_then(|body| parse_assignment(body, "water_line_segment"))
+    {
+        out.water_lines = flatten(&items);
+    }
+
+    if let Some(body) = sub_body(input, "add_seaweed") {
+        out.seaweed_segments = extract_appended_strings(body);
+    }
+
+    if let Some(body) = sub_body(input, "add_whale") {
+        // `if($dir) { $spout_align = 1; ... } else { $spout_align = 11; ... }`
+        if let [left, right] = scalar_numbers(body, "spout_align")[..] {
+            out.whale_spout_align = (right, left);
+        }
+    }
+
+    out
+}
+
+/// Extract fish art blocks (with masks) from a specific Perl function:
+/// - Locates `sub <func_name>`
+/// - Parses the `my @fish_image = (...)` list
+/// - Pairs even-indexed art blocks with the odd-indexed mask that follows
+fn extract_fish_blocks_from_function(input: &str, func_name: &str) -> Vec<FishBlock> {
+    let Some(items) = sub_body(input, func_name).and_then(|b| parse_assignment(b, "fish_image"))
+    else {
+        return Vec::new();
+    };
+    let blocks = flatten(&items);
+    blocks
+        .chunks(2)
+        .map(|pair| FishBlock {
+            art: pair[0].clone(),
+            mask: pair.get(1).cloned(),
+        })
+        .collect()
+}
+
+fn extract_sprites(input: &str, spec: &SpriteSpec) -> Vec<Sprite> {
+    let Some(body) = sub_body(input, spec.sub_name) else {
+        return Vec::new();
+    };
+    let Some(images) = parse_assignment(body, spec.image_var) else {
+        return Vec::new();
+    };
+    let masks = spec
+        .mask_var
+        .and_then(|var| parse_assignment(body, var))
+        .unwrap_or_default();
+    let transparency = entity_transparency(body);
+
+    match spec.layout {
+        SpriteLayout::Directional => ["RIGHT", "LEFT"]
+            .iter()
+            .enumerate()
+            .filter_map(|(i, dir)| {
+                let frames = images.get(i).map(PerlValue::strings)?;
+                Some(Sprite {
+                    name: format!("{}_{}", spec.name, dir),
+                    frames,
+                    masks: masks.get(i).map(PerlValue::strings).unwrap_or_default(),
+                    transparency,
+                })
+            })
+            .collect(),
+        SpriteLayout::Frames | SpriteLayout::Single => vec![Sprite {
+            name: spec.name.to_string(),
+            frames: flatten(&images),
+            masks: flatten(&masks),
+            transparency,
+        }],
+    }
+}
+
+/// Transparency options passed to `new_entity` in a sub. Helper entities without options
+/// (e.g. the shark's invisible teeth) share the sub, so any `auto_trans` wins. Only the
+/// code of the argument lists counts; comments and strings elsewhere in the sub do not.
+fn entity_transparency(body: &str) -> SpriteTransparency {
+    let lists = entity_arguments(body);
+    if lists.iter().any(|l| l.contains("auto_trans=>1")) {
+        SpriteTransparency::Auto
+    } else if lists.iter().any(|l| l.contains("transparent=>")) {
+        SpriteTransparency::Spaces
+    } else {
+        SpriteTransparency::Opaque
+    }
+}
+
+/// Argument lists of the `new_entity(...)` and `Term::Animation::Entity->new(...)` calls in
+/// `body`, as code only: whitespace, comments and the contents of string literals removed
+/// (`transparent => ' ',` becomes `transparent=>'',`).
+fn entity_arguments(body: &str) -> Vec<String> {
+    let mut lists = Vec::new();
+    for call in ["new_entity(", "Term::Animation::Entity->new("] {
+        let mut rest = body;
+        while let Some(idx) = rest.find(call) {
+            rest = &rest[idx + call.len()..];
+            let (code, used) = call_arguments(rest);
+            lists.push(code);
+            rest = &rest[used..];
+        }
+    }
+    lists
+}
+
+/// Code up to the `)` closing an argument list that starts at `src`, and the bytes used.
+fn call_arguments(src: &str) -> (String, usize) {
+    let mut code = String::new();
+    let mut depth = 0usize;
+    let mut pos = 0;
+    let mut prev = ' ';
+    while let Some(c) = src[pos..].chars().next() {
+        pos += c.len_utf8();
+        let rest = &src[pos..];
+        let word_start = !(prev.is_alphanumeric() || matches!(prev, '_' | '$' | '@'));
+        prev = c;
+        // Literals are skipped whole; only their delimiters are kept.
+        let skipped = match c {
+            '\'' => parse_delimited(rest, '\'').map(|(_, n)| n),
+            '"' => parse_double_quoted(rest).map(|(_, n)| n),
+            'q' if word_start && rest.starts_with('{') => {
+                parse_q_brace(&rest[1..]).map(|(_, n)| n + 1)
+            }
+            '#' => Some(rest.find('\n').unwrap_or(rest.len())),
+            _ => None,
+        };
+        if let Some(n) = skipped {
+            if c != '#' {
+                code.push(c);
+                code.push(c);
+            }
+            pos += n;
+            continue;
+        }
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' if depth == 0 => return (code, pos),
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            c if c.is_whitespace() => continue,
+            _ => {}
+        }
+        code.push(c);
+    }
+    (code, pos)
+}
+
+/// Numbers assigned to `$var` (`$var = N;`), in source order.
+fn scalar_numbers(body: &str, var: &str) -> Vec<usize> {
+    let needle = format!("${}", var);
+    let mut out = Vec::new();
+    let mut rest = body;
+    while let Some(idx) = rest.find(&needle) {
+        rest = rest[idx + needle.len()..].trim_start();
+        let Some(value) = rest.strip_prefix('=') else {
+            continue;
+        };
+        let digits: String = value
+            .trim_start()
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        if let Ok(n) = digits.parse() {
+            out.push(n);
+        }
+    }
+    out
+}
+
+/// Collect the double-quoted strings appended with `.=` (used for the seaweed stalk).
+fn extract_appended_strings(body: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut rest = body;
+    while let Some(idx) = rest.find(".=") {
+        rest = rest[idx + 2..].trim_start();
+        if let Some((PerlValue::Str(s), used)) = parse_value(rest) {
+            out.push(s);
+            rest = &rest[used..];
+        }
+    }
+    out
+}
+
+/// A parsed Perl literal: a string or a (possibly nested) list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PerlValue {
+    Str(String),
+    List(Vec<PerlValue>),
+}
+
+impl PerlValue {
+    /// All strings in this value, depth first.
+    fn strings(&self) -> Vec<String> {
+        match self {
+            PerlValue::Str(s) => vec![s.clone()],
+            PerlValue::List(items) => flatten(items),
+        }
+    }
+}
+
+fn flatten(items: &[PerlValue]) -> Vec<String> {
+    items.iter().flat_map(PerlValue::strings).collect()
+}
+
+/// The source of `sub <name>` up to the next top-level `sub`.
+fn sub_body<'a>(input: &'a str, name: &str) -> Option<&'a str> {
+    let marker = format!("sub {}", name);
+    let mut search = 0;
+    while let Some(rel) = input[search..].find(&marker) {
+        let start = search + rel;
+        let after = &input[start + marker.len()..];
+        // Require a word boundary so `add_fish` does not match `add_fish_entity`.
+        if after.starts_with(|c: char| c.is_whitespace() || c == '{') {
+            let end = after.find("\nsub ").unwrap_or(after.len());
+            return Some(&after[..end]);
+        }
+        search = start + marker.len();
+    }
+    None
+}
+
+/// Parse the literal assigned by `my @var = ...;` or `my $var = ...;` in `body`.
+/// Scalars are returned as a one-element list.
+fn parse_assignment(body: &str, var: &str) -> Option<Vec<PerlValue>> {
+    for sigil in ['@', '$'] {
+        let marker = format!("my {}{}", sigil, var);
+        let mut search = 0;
+        while let Some(rel) = body[search..].find(&marker) {
+            let start = search + rel + marker.len();
+            search = start;
+            let after = body[start..].trim_start();
+            let Some(rhs) = after.strip_prefix('=') else {
+                continue;
+            };
+            return match parse_value(rhs.trim_start()) {
+                Some((PerlValue::List(items), _)) => Some(items),
+                Some((value, _)) => Some(vec![value]),
+                None => None,
+            };
+        }
+    }
+    None
+}
+
+/// Parse one literal at the start of `src`. Returns the value and the bytes consumed.
+pub fn parse_value(src: &str) -> Option<(PerlValue, usize)> {
+    if let Some(rest) = src.strip_prefix('(') {
+        let (items, used) = parse_list(rest, ')')?;
+        Some((PerlValue::List(items), used + 1))
+    } else if let Some(rest) = src.strip_prefix('[') {
+        let (items, used) = parse_list(rest, ']')?;
+        Some((PerlValue::List(items), used + 1))
+    } else if let Some(rest) = src.strip_prefix("q{") {
+        let (s, used) = parse_q_brace(rest)?;
+        Some((PerlValue::Str(s), used + 2))
+    } else if let Some(rest) = src.strip_prefix("q#") {
+        let (s, used) = parse_delimited(rest, '#')?;
+        Some((PerlValue::Str(s), used + 2))
+    } else if let Some(rest) = src.strip_prefix('"') {
+        let (s, used) = parse_double_quoted(rest)?;
+        Some((PerlValue::Str(s), used + 1))
+    } else if let Some(rest) = src.strip_prefix('\'') {
+        let (s, used) = parse_delimited(rest, '\'')?;
+        Some((PerlValue::Str(s), used + 1))
+    } else {
+        None
+    }
+}
+
+/// Parse list items up to and including `close`. Returns items and bytes consumed.
+fn parse_list(src: &str, close: char) -> Option<(Vec<PerlValue>, usize)> {
+    let mut items = Vec::new();
+    let mut pos = 0;
+    loop {
+        pos += skip_separators(&src[pos..]);
+        let rest = &src[pos..];
+        if rest.starts_with(close) {
+            return Some((items, pos + close.len_utf8()));
+        }
+        let (value, used) = parse_value(rest)?;
+        items.push(value);
+        pos += used;
+    }
+}
+
+/// Skip whitespace, commas and `#` comments. Returns bytes skipped.
+fn skip_separators(src: &str) -> usize {
+    let mut pos = 0;
+    loop {
+        let rest = &src[pos..];
+        let trimmed = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        pos += rest.len() - trimmed.len();
+        if trimmed.starts_with('#') {
+            pos += trimmed.find('\n').unwrap_or(trimmed.len());
+        } else {
+            return pos;
+        }
+    }
+}
+
+/// Body of a `q{...}` literal (nested braces allowed). `\\`, `\{` and `\}` are unescaped.
+fn parse_q_brace(src: &str) -> Option<(String, usize)> {
+    let mut out = String::new();
+    let mut depth = 0usize;
+    let mut chars = src.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, e @ ('\\' | '{' | '}'))) => out.push(e),
+                Some((_, e)) => {
+                    out.push('\\');
+                    out.push(e);
+                }
+                None => out.push('\\'),
+            },
+            '{' => {
+                depth += 1;
+                out.push(c);
+            }
+            '}' if depth == 0 => return Some((finish_block(&out), i + 1)),
+            '}' => {
+                depth -= 1;
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    None
+}
+
+/// Body of a single-quote-style literal closed by `delim` (`q#...#`, `'...'`).
+fn parse_delimited(src: &str, delim: char) -> Option<(String, usize)> {
+    let mut out = String::new();
+    let mut chars = src.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some((_, e)) if e == '\\' || e == delim => out.push(e),
+                Some((_, e)) => {
+                    out.push('\\');
+                    out.push(e);
+                }
+                None => out.push('\\'),
+            }
+        } else if c == delim {
+            return Some((finish_block(&out), i + c.len_utf8()));
+        } else {
+            out.push(c);
+        }
+    }
+    None
+}
+
+/// Body of a `"..."` literal. Handles the escapes the original uses (no interpolation).
+fn parse_double_quoted(src: &str) -> Option<(String, usize)> {
+    let mut out = String::new();
+    let mut chars = src.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, 'n')) => out.push('\n'),
+                Some((_, 't')) => out.push('\t'),
+                Some((_, e)) => out.push(e),
+                None => out.push('\\'),
+            },
+            '"' => return Some((finish_block(&out), i + 1)),
+            _ => out.push(c),
+        }
+    }
+    None
+}
+
+/// Normalise a literal body: drop the newline that follows the opening delimiter (art
+/// starts on the next line) and trailing newlines, keeping interior blank lines.
+fn finish_block(s: &str) -> String {
+    let s = s.replace("\r\n", "\n");
+    let s = s.strip_prefix('\n').unwrap_or(&s);
+    trim_trailing_newlines(s)
+}
+
+/// Trim trailing newlines for a cleaner const block, but preserve interior newlines.
+fn trim_trailing_newlines(s: &str) -> String {
+    let mut out = s.to_string();
+    while out.ends_with('\n') {
+        out.pop();
+    }
+    out
+}
+
+/// Generate the Rust module source containing constants and a helper function.
+///
+/// The generated module depends only on `FishArt`/`Facing` and `GeneratedSprite`. It exposes:
+/// - const FISH_N / FISH_MASK_N: &str = "...";
+/// - GeneratedSprite constants for every creature, plus water line and seaweed segments
+/// - pub fn get_generated_fish_assets() -> Vec<FishArt> (pairs declare facing and flips).
+pub fn generate_rust_module(extracted: &Extracted) -> String {
+    let mut out = String::new();
+
+    // Plain comments (not `//!`) so the output can also be `include!`d from `OUT_DIR`.
+    out.push_str("// AUTO-GENERATED FILE: Do not edit by hand.\n");
+    out.push_str("//\n");
+    out.push_str(
+        "// Generated by `src/extract.rs` (via `extract_fish` or the `build-assets` build script)\n",
+    );
+    out.push_str(
+        "// from the original Perl Asciiquarium script. Every creature's ASCII art and colour\n",
+    );
+    out.push_str("// masks as constants, plus a helper to build Vec<FishArt> for the widget.\n\n");
+
+    out.push_str("use super::asciiquarium::{Facing, FishArt, Transparency};\n");
+    out.push_str("use super::asciiquarium_assets::GeneratedSprite;\n\n");
+
+    for (i, fish) in extracted.fish.iter().enumerate() {
+        out.push_str(&format!(
+            "pub const FISH_{:04}: &str = \"{}\";\n",
+            i + 1,
+            escape_for_rust_string(&fish.art)
+        ));
+        if let Some(mask) = &fish.mask {
+            out.push_str(&format!(
+                "pub const FISH_MASK_{:04}: &str = \"{}\";\n",
+                i + 1,
+                escape_for_rust_string(mask)
+            ));
+        }
+    }
+
+    for sprite in &extracted.sprites {
+        out.push_str(&format!(
+            "\npub const {}: GeneratedSprite = GeneratedSprite {{\n",
+            sprite.name
+        ));
+        push_str_slice(&mut out, "frames", &sprite.frames);
+        push_str_slice(&mut out, "masks", &sprite.masks);
+        out.push_str(&format!(
+            "    transparency: Transparency::{},\n",
+            sprite.transparency.rust_name()
+        ));
+        out.push_str("};\n");
+    }
+
+    out.push_str("\n/// Surface segments, tiled across the width (top row first).\n");
+    push_str_array(&mut out, "WATER_LINE_SEGMENTS", &extracted.water_lines);
+    out.push_str("\n/// Seaweed stalk segments; the original alternates them up the stalk.\n");
+    push_str_array(&mut out, "SEAWEED_SEGMENTS", &extracted.seaweed_segments);
+    out.push_str(
+        "\n/// Columns from the whale's left edge to the spout frames (right-moving, left-moving).\n",
+    );
+    out.push_str(&format!(
+        "pub const WHALE_SPOUT_ALIGN: (usize, usize) = ({}, {});\n",
+        extracted.whale_spout_align.0, extracted.whale_spout_align.1
+    ));
+
+    out.push_str(
+        "\n/// The original lists fish as right/left pairs: even entries face right, odd entries\n",
+    );
+    out.push_str(
+        "/// face left, and each uses its partner as the hand-drawn opposite-facing art.\n",
+    );
+    out.push_str("pub fn get_generated_fish_assets() -> Vec<FishArt> {\n");
+    out.push_str("    let mut out = Vec::new();\n");
+    if !extracted.fish.is_empty() {
+        out.push_str("    let arts: &[(&'static str, Option<&'static str>)] = &[\n");
+        for (i, fish) in extracted.fish.iter().enumerate() {
+            if fish.mask.is_some() {
+                out.push_str(&format!(
+                    "        (FISH_{:04}, Some(FISH_MASK_{:04})),\n",
+                    i + 1,
+                    i + 1
+                ));
+            } else {
+                out.push_str(&format!("        (FISH_{:04}, None),\n", i + 1));
+            }
+        }
+        out.push_str("    ];\n");
+        out.push_str("    for (i, (art, mask)) in arts.iter().enumerate() {\n");
+        out.push_str("        let (facing, partner) = if i % 2 == 0 {\n");
+        out.push_str("            (Facing::Right, arts.get(i + 1))\n");
+        out.push_str("        } else {\n");
+        out.push_str("            (Facing::Left, arts.get(i - 1))\n");
+        out.push_str("        };\n");
+        out.push_str("        let mut fish = FishArt::new(art).with_facing(facing);\n");
+        out.push_str("        if let Some((partner_art, partner_mask)) = partner {\n");
+        out.push_str("            fish = fish.with_flipped(partner_art);\n");
+        out.push_str("            if let Some(partner_mask) = partner_mask {\n");
+        out.push_str("                fish = fish.with_flipped_mask(partner_mask);\n");
+        out.push_str("            }\n");
+        out.push_str("        }\n");
+        out.push_str("        if let Some(mask) = mask {\n");
+        out.push_str("            fish = fish.with_mask(mask);\n");
+        out.push_str("        }\n");
+        out.push_str("        out.push(fish);\n");
+        out.push_str("    }\n");
+    }
+    out.push_str("    out\n");
+    out.push_str("}\n");
+
+    out
+}
+
+fn push_str_slice(out: &mut String, field: &str, items: &[String]) {
+    if items.is_empty() {
+        out.push_str(&format!("    {}: &[],\n", field));
+        return;
+    }
+    out.push_str(&format!("    {}: &[\n", field));
+    for item in items {
+        out.push_str(&format!("        \"{}\",\n", escape_for_rust_string(item)));
+    }
+    out.push_str("    ],\n");
+}
+
+fn push_str_array(out: &mut String, name: &str, items: &[String]) {
+    out.push_str(&format!(
+        "pub const {}: [&str; {}] = [\n",
+        name,
+        items.len()
+    ));
+    for item in items {
+        out.push_str(&format!("    \"{}\",\n", escape_for_rust_string(item)));
+    }
+    out.push_str("];\n");
+}
+
+/// Escape a string for inclusion in a standard Rust string literal.
+/// - Escapes backslashes and double quotes
+/// - Converts CRLF to LF
+/// - Preserves newlines as \\n
+fn escape_for_rust_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() * 2);
+    for ch in s.replace("\r\n", "\n").chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '\"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_crate_file(rel: &str) -> String {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(rel);
+        std::fs::read_to_string(path).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_trim_trailing_newlines() {
+        assert_eq!(trim_trailing_newlines("abc\n"), "abc");
+        assert_eq!(trim_trailing_newlines("abc\n\n"), "abc");
+        assert_eq!(trim_trailing_newlines("abc"), "abc");
+    }
+
+    #[test]
+    fn test_escape_for_rust_string() {
+        let s = "a\\b\"c\nd";
+        let e = escape_for_rust_string(s);
+        assert_eq!(e, "a\\\\b\\\"c\\nd");
+    }
+
+    #[test]
+    fn test_generate_empty_module() {
+        let m = generate_rust_module(&Extracted::default());
+        assert!(m.contains("get_generated_fish_assets"));
+        // Should not include arts array if empty
+        assert!(!m.contains("let arts: &["));
+    }
+
+    #[test]
+    fn test_parse_perl_literals() {
+        let src = "(\nq{\n \\\\ \\{x\\}\n},\n[ \"a\\\\b\n\", q#\nc\\##, ],\n);";
+        let (value, _) = parse_value(src).unwrap_or((PerlValue::List(Vec::new()), 0));
+        assert_eq!(
+            value,
+            PerlValue::List(vec![
+                PerlValue::Str(" \\ {x}".to_string()),
+                PerlValue::List(vec![
+                    PerlValue::Str("a\\b".to_string()),
+                    PerlValue::Str("c#".to_string()),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn transparency_comes_from_entity_arguments_only() {
+        let body = r#"
+            # Not transparent => ' ' here, this is a comment.
+            my $note = "transparent => 1";
+            $anim->new_entity(
+                shape => q{
+ transparent=>
+},
+                position => [ $x, $y ], # transparent => ' '
+                color => 'transparent=>',
+            );
+        "#;
+        assert_eq!(entity_transparency(body), SpriteTransparency::Opaque);
+        let spaces = body.replace("color =>", "transparent\t=> ' ', color =>");
+        assert_eq!(entity_transparency(&spaces), SpriteTransparency::Spaces);
+        let auto = format!("{body}\nTerm::Animation::Entity->new(auto_trans => 1);");
+        assert_eq!(entity_transparency(&auto), SpriteTransparency::Auto);
+    }
+
+    #[test]
+    fn test_extracts_every_creature() {
+        let input = read_crate_file(ORIGINAL_SCRIPT_PATH);
+        let extracted = extract_all(&input);
+        assert_eq!(extracted.fish.len(), 24);
+        assert!(extracted.fish.iter().all(|f| f.mask.is_some()));
+        for name in ["SHARK_RIGHT", "WHALE_LEFT", "SHIP_RIGHT", "CASTLE", "SPLAT"] {
+            assert!(extracted.sprites.iter().any(|s| s.name == name), "{name}");
+        }
+        let monster = extracted
+            .sprites
+            .iter()
+            .find(|s| s.name == "OLD_MONSTER_LEFT");
+        assert_eq!(monster.map(|m| m.frames.len()), Some(4));
+        let transparency = |name: &str| {
+            extracted
+                .sprites
+                .iter()
+                .find(|s| s.name == name)
+                .map(|s| s.transparency)
+        };
+        assert_eq!(transparency("SHARK_RIGHT"), Some(SpriteTransparency::Auto));
+        assert_eq!(transparency("CASTLE"), Some(SpriteTransparency::Opaque));
+        assert_eq!(transparency("SPLAT"), Some(SpriteTransparency::Spaces));
+        assert_eq!(extracted.water_lines.len(), 4);
+        assert_eq!(extracted.seaweed_segments, vec!["(", " )"]);
+        assert_eq!(extracted.whale_spout_align, (11, 1));
+    }
+
+    #[test]
+    fn committed_generated_module_is_up_to_date() {
+        let input = read_crate_file(ORIGINAL_SCRIPT_PATH);
+        let committed = read_crate_file(GENERATED_MODULE_PATH);
+        assert!(
+            committed == generate_from_script(&input),
+            "{} is stale; run `cargo run --bin extract_fish`",
+            GENERATED_MODULE_PATH
+        );
+    }
+}
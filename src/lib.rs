@@ -5,6 +5,9 @@ Agent Log:
 - Refactored crate root to use an external `widgets` module file (src/widgets/mod.rs) for clean path resolution.
 - The `widgets` module will publicly expose `asciiquarium` and `asciiquarium_assets`.
 - Kept ergonomic re-exports at the crate root for common types and functions.
+- Added `extract`: the Perl asset parser/generator used by `extract_fish`. It is `#[doc(hidden)]`
+  (tooling, not API). `build.rs` cannot link the crate it builds, so it includes the same file
+  with `#[path]`.
 
 Next steps for agents:
 - Ensure `src/widgets/mod.rs` declares:
@@ -14,12 +17,43 @@ Next steps for agents:
 
 #![forbid(unsafe_code)]
 
+#[doc(hidden)]
+pub mod extract;
 pub mod widgets;
 
 // Re-export common items for convenience at the crate root.
+#[cfg(feature = "image")]
+pub use widgets::animation_export::{
+    render_aquarium_animation, AnimationError, AnimationFormat, AnimationOptions,
+};
+pub use widgets::asciicast::{render_aquarium_to_ansi, render_aquarium_to_asciicast, CastOptions};
 pub use widgets::asciiquarium::{
-    render_aquarium_to_string, update_aquarium, AquariumState, AsciiquariumTheme,
-    AsciiquariumWidget, FishArt, FishInstance,
+    render_aquarium_to_grid, render_aquarium_to_string, resize_aquarium, scare_fish,
+    set_aquarium_size, update_aquarium, AquariumState, AsciiquariumTheme, AsciiquariumWidget,
+    Cruise, Facing, FishArt, FishInstance, Transparency,
 };
 pub use widgets::asciiquarium_assets::{get_fish_assets, measure_art};
+pub use widgets::asciiquarium_painter::AsciiquariumPainter;
+pub use widgets::asset_registry::{AssetRegistry, AssetTag};
+pub use widgets::auto_fit::fit_aquarium_to_ui;
+pub use widgets::bubbles::{BubbleEmitter, EmitterAnchor};
+pub use widgets::clock::SimClock;
+pub use widgets::current::{Current, Eddy};
+pub use widgets::day_night::{hour_from_unix_seconds, DayClock, DayNight};
+pub use widgets::decorations::{Decoration, DecorationAnchor, DecorationAnimation, DecorationKind};
+pub use widgets::editing::{add_fish_at, move_entity, remove_entity};
+pub use widgets::food::drop_food;
 pub use widgets::get_all_fish_assets;
+pub use widgets::hit_test::entity_at;
+pub use widgets::html_export::{
+    render_aquarium_to_animated_html, render_aquarium_to_html, render_aquarium_to_svg,
+};
+pub use widgets::interactive::{AquariumResponse, AsciiquariumInteractive, AssetDrop};
+pub use widgets::layout::{apply_layout, capture_layout, LayoutError, TankLayout};
+#[cfg(feature = "image")]
+pub use widgets::png_export::{render_aquarium_to_image, render_aquarium_to_png};
+pub use widgets::seabed::Seabed;
+pub use widgets::stream::{
+    apply_control, stream_frame, ControlError, ControlMessage, FrameStream, StreamFrame,
+};
+pub use widgets::weather::{Weather, WeatherKind, WeatherSpell};
@@ -0,0 +1,262 @@
+/*!
+asciicast v2 export: a simulated run as an asciinema `.cast` recording.
+
+Agent Log:
+- Added for terminal users who share recordings with asciinema. The header takes its width
+  and height from `state.size`; every frame is an `"o"` (output) event stamped with its time.
+- Frames are ANSI text in 24-bit colour, coloured like the egui widget (`shade_color`: palette,
+  layer and day/night shade) over the theme background when there is one.
+- The first frame clears the screen and draws everything; with `CastOptions::diffs` (the
+  default) later frames only redraw, per row, the span of cells that changed. Wide glyphs are
+  redrawn whole.
+- The snapshot is cloned and simulated; frame `i` shows the first tick at or after `i / fps`
+  seconds (`CLASSIC_DT` per tick), so any fps works with the fixed simulation step.
+- JSON is written by hand (`json_string`); the crate has no serde.
+*/
+
+use std::fmt::Write;
+
+use egui::Color32;
+
+use super::asciiquarium::{
+    render_aquarium_to_grid, update_aquarium, AquariumState, AsciiquariumTheme, FishArt, CLASSIC_DT,
+};
+use super::cell_grid::{Cell, CellGrid};
+use super::day_night::{shade_color, shade_for, Shade};
+
+/// What to record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastOptions {
+    /// Frames per second.
+    pub fps: f32,
+    /// Length of the recording in seconds.
+    pub duration: f32,
+    /// Redraw only changed cells after the first frame.
+    pub diffs: bool,
+    pub title: Option<String>,
+}
+
+impl CastOptions {
+    /// `duration` seconds at `fps`, with diffs.
+    pub fn new(fps: f32, duration: f32) -> Self {
+        Self {
+            fps,
+            duration,
+            diffs: true,
+            title: None,
+        }
+    }
+
+    pub fn with_diffs(mut self, diffs: bool) -> Self {
+        self.diffs = diffs;
+        self
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Frames recorded (at least one).
+    pub fn frame_count(&self) -> usize {
+        ((self.duration * self.fps).floor() as usize).max(1)
+    }
+}
+
+/// One rendered cell: its glyph (empty for the right half of a wide glyph) and colour.
+type FrameCell = (String, Color32);
+
+fn frame_cells(
+    grid: &CellGrid<'_>,
+    theme: &AsciiquariumTheme,
+    shade: Option<&Shade>,
+) -> Vec<FrameCell> {
+    let mut cells = Vec::with_capacity(grid.width * grid.height);
+    for y in 0..grid.height {
+        for x in 0..grid.width {
+            cells.push(match grid.get(x, y) {
+                Some(Cell::Glyph(g)) => (g.to_string(), shade_color(grid, x, y, g, theme, shade)),
+                _ => (String::new(), Color32::TRANSPARENT),
+            });
+        }
+    }
+    cells
+}
+
+fn sgr_fg(out: &mut String, color: Color32) {
+    let [r, g, b, _] = color.to_array();
+    let _ = write!(out, "\x1b[38;2;{r};{g};{b}m");
+}
+
+/// Cells `x0..x1` of `row`, starting with the cursor at `x0`.
+fn push_span(out: &mut String, row: &[FrameCell], x0: usize, x1: usize) {
+    let mut current = None;
+    for (glyph, color) in &row[x0..x1] {
+        if glyph.is_empty() {
+            continue;
+        }
+        if current != Some(*color) {
+            sgr_fg(out, *color);
+            current = Some(*color);
+        }
+        out.push_str(glyph);
+    }
+}
+
+/// Background SGR for the theme under `shade`, if the theme has a background.
+fn background_sgr(theme: &AsciiquariumTheme, shade: Option<&Shade>) -> String {
+    theme.background.map_or_else(String::new, |fill| {
+        let [r, g, b, _] = shade.map_or(fill, |s| s.background(fill)).to_array();
+        format!("\x1b[48;2;{r};{g};{b}m")
+    })
+}
+
+/// ANSI text drawing `cells` in full: clear, home, then every row.
+fn full_frame(cells: &[FrameCell], width: usize, background: &str) -> String {
+    let mut out = format!("\x1b[?25l{background}\x1b[2J");
+    for (y, row) in cells.chunks(width.max(1)).enumerate() {
+        let _ = write!(out, "\x1b[{};1H", y + 1);
+        push_span(&mut out, row, 0, row.len());
+    }
+    out
+}
+
+/// ANSI text redrawing, row by row, the span of cells that differ from `previous`.
+fn diff_frame(previous: &[FrameCell], cells: &[FrameCell], width: usize) -> String {
+    let mut out = String::new();
+    let rows = previous
+        .chunks(width.max(1))
+        .zip(cells.chunks(width.max(1)));
+    for (y, (before, after)) in rows.enumerate() {
+        let changed = |x: &usize| before[*x] != after[*x];
+        let (Some(mut x0), Some(last)) = (
+            (0..after.len()).find(changed),
+            (0..after.len()).rev().find(changed),
+        ) else {
+            continue;
+        };
+        // Start on the left half of a wide glyph.
+        if x0 > 0 && after[x0].0.is_empty() {
+            x0 -= 1;
+        }
+        let _ = write!(out, "\x1b[{};{}H", y + 1, x0 + 1);
+        push_span(&mut out, after, x0, last + 1);
+    }
+    out
+}
+
+/// The tank as one ANSI frame (24-bit colour, clears the screen first).
+pub fn render_aquarium_to_ansi(
+    state: &AquariumState,
+    assets: &[FishArt],
+    theme: &AsciiquariumTheme,
+) -> String {
+    let grid = render_aquarium_to_grid(state, assets);
+    let shade = shade_for(state);
+    let cells = frame_cells(&grid, theme, shade.as_ref());
+    let mut out = full_frame(&cells, grid.width, &background_sgr(theme, shade.as_ref()));
+    out.push_str("\x1b[0m");
+    out
+}
+
+/// `s` as a JSON string literal.
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn push_event(out: &mut String, time: f32, data: &str) {
+    let _ = writeln!(out, "[{:.6}, \"o\", {}]", time, json_string(data));
+}
+
+/// Simulate a copy of `state` and record it as an asciicast v2 file.
+pub fn render_aquarium_to_asciicast(
+    state: &AquariumState,
+    assets: &[FishArt],
+    theme: &AsciiquariumTheme,
+    options: &CastOptions,
+) -> String {
+    let mut state = state.clone();
+    let (width, height) = state.size;
+    let mut out = format!(
+        "{{\"version\": 2, \"width\": {width}, \"height\": {height}, \"env\": {{\"TERM\": \"xterm-256color\"}}"
+    );
+    if let Some(title) = &options.title {
+        let _ = write!(out, ", \"title\": {}", json_string(title));
+    }
+    out.push_str("}\n");
+
+    let fps = options.fps.max(f32::EPSILON);
+    let start = state.tick;
+    let mut previous: Option<(Vec<FrameCell>, String)> = None;
+    for i in 0..options.frame_count() {
+        let time = i as f32 / fps;
+        while ((state.tick - start) as f32) * CLASSIC_DT < time {
+            update_aquarium(&mut state, assets);
+        }
+        let grid = render_aquarium_to_grid(&state, assets);
+        let shade = shade_for(&state);
+        let cells = frame_cells(&grid, theme, shade.as_ref());
+        let background = background_sgr(theme, shade.as_ref());
+        let frame = match &previous {
+            // A new background (day/night) needs a full redraw.
+            Some((before, bg)) if options.diffs && *bg == background => {
+                diff_frame(before, &cells, width)
+            }
+            _ => full_frame(&cells, width, &background),
+        };
+        if !frame.is_empty() {
+            push_event(&mut out, time, &frame);
+        }
+        previous = Some((cells, background));
+    }
+    push_event(
+        &mut out,
+        options.frame_count() as f32 / fps,
+        "\x1b[0m\x1b[?25h",
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_header_and_timed_diff_events() {
+        let state = AquariumState {
+            size: (40, 10),
+            ..Default::default()
+        };
+        let theme = AsciiquariumTheme::default();
+        let options = CastOptions::new(10.0, 1.0).with_title("tank \"1\"");
+        let cast = render_aquarium_to_asciicast(&state, &[], &theme, &options);
+        let lines: Vec<&str> = cast.lines().collect();
+        assert!(lines[0].starts_with("{\"version\": 2, \"width\": 40, \"height\": 10"));
+        assert!(lines[0].ends_with("\"title\": \"tank \\\"1\\\"\"}"));
+        assert!(lines[1].starts_with("[0.000000, \"o\", \"\\u001b[?25l"));
+        assert!(lines.last().is_some_and(|l| l.starts_with("[1.000000, ")));
+        // Later frames are diffs: no screen clears, and shorter than full redraws.
+        assert!(lines[2..].iter().all(|l| !l.contains("[2J")));
+
+        let full = render_aquarium_to_asciicast(&state, &[], &theme, &options.with_diffs(false));
+        assert_eq!(full.lines().count(), 12);
+        assert!(cast.len() < full.len() / 2);
+        assert_eq!(state.tick, 0);
+    }
+}
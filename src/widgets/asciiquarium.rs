@@ -8,26 +8,192 @@ Agent Log:
 - Updated rendering to draw waterlines, castle, seaweed, fishes, then bubbles (top-most).
 - Preserved stateless widget and single-label rendering approach.
 - Kept bounce physics and clipping; float-to-int via floor() for stability.
+- FishArt declares its facing and carries precomputed flipped art/mask; rendering picks a variant instead of mirroring per frame.
+- Sprites are drawn through `blit` with Term::Animation transparency (leading whitespace and `?` see-through for `auto_trans`).
+- Added `resize_aquarium`: proportional relocation, kept seaweed, re-derived visitor depths, density-preserving fish re-seed.
+- Frames are composed into a `CellGrid` of grapheme clusters; widths, mirroring and clipping use display width.
+- The castle is one of `env.decorations` (see `decorations`), drawn behind or in front of fish by depth.
+- Grid cells are tagged with the `Layer` that drew them, and the optional `env.day_night` cycle
+  shades colours per layer and row (see `day_night`); the moon's reflection is drawn as glyphs.
+- The surface follows `env.weather` (see `weather`): wave shape, ship rocking and raindrops.
+- An optional `env.current` (see `current`) drifts bubbles and small fish and bends seaweed.
+- Bubbles grow and pop at the surface, and `env.emitters` adds bubble sources (see `bubbles`).
+- Whales spout periodically from the blowhole anchor declared with their art, and ships leave a
+  fading wake on the waterline (see `wake`).
+- Food pellets in `env.food` sink and draw nearby fish, which eat them (see `food`).
 */
 
+use std::borrow::Cow;
+
 use egui;
 
+use super::asset_registry::{is_small_fish, pick_seeded};
+use super::bubbles::{detach_emitters, emit_bubbles, update_bubbles, BubbleEmitter};
+use super::cell_grid::{Cell, CellGrid, Layer};
+use super::current::{current_at, seaweed_lean, Current, SMALL_FISH_DRIFT};
+use super::day_night::{moon_reflection, shade_for, DayNight};
+use super::decorations::{Decoration, DecorationAnchor};
+use super::food::{food_cells, update_food, Pellet};
+use super::generated_assets as generated;
+use super::seabed::{floor_under, seabed_cells, Seabed};
+use super::text_width::{cells_of, display_width, grapheme_width, graphemes};
+use super::wake::{update_wake, wake_cells, WakeCell};
+use super::weather::{rain_cells, ship_bob, update_weather, wave_offset, Weather, WeatherKind};
+
 const CLASSIC_BUBBLE_TICKS: u64 = 24;
-const CLASSIC_DT: f32 = 0.033;
+/// Seconds simulated by one `update_aquarium` tick.
+pub const CLASSIC_DT: f32 = 0.033;
 const CLASSIC_FISH_SPEED_MULT: f32 = 2.0;
+/// Vertical speed of a fish swimming up or down toward something; scared fish dive at four
+/// times this.
+pub(crate) const CRUISE_MAX_VY: f32 = 0.6;
+/// Per-tick velocity damping while a scared fish calms down.
+const DART_DAMPING: f32 = 0.97;
+/// Speed difference (cells per second) under which a darting fish is back to cruising.
+const DART_SETTLE: f32 = 0.1;
+/// Speed a scared fish darts away at.
+const SCARE_SPEED: f32 = 45.0;
+/// Fish whose centre is within this many cells of a scare flee.
+pub const SCARE_RADIUS: f32 = 10.0;
+
+/// Horizontal direction an art block faces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Facing {
+    Left,
+    Right,
+}
+
+impl Facing {
+    pub fn opposite(self) -> Self {
+        match self {
+            Facing::Left => Facing::Right,
+            Facing::Right => Facing::Left,
+        }
+    }
+}
+
+/// Which blank cells of an art block let the layers behind show through, following
+/// Term::Animation. `?` is always transparent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Transparency {
+    /// `auto_trans`: leading whitespace on each line is transparent; interior spaces are
+    /// opaque, so bodies hide what is behind them.
+    #[default]
+    Auto,
+    /// Spaces are opaque (the castle, water lines).
+    Opaque,
+    /// Every space is transparent (the splat).
+    Spaces,
+}
 
 /// Visual asset for a fish (ASCII art and its measured dimensions).
-#[derive(Debug, Clone, Copy)]
+///
+/// The opposite-facing variant is prepared once when the asset is built, so rendering
+/// never mirrors per frame. Build with `FishArt::new` and the `with_*` methods.
+#[derive(Debug, Clone)]
 pub struct FishArt {
     pub art: &'static str,
     pub width: usize,
     pub height: usize,
+    /// Direction `art` faces (declared, or guessed by a heuristic in `new`).
+    pub facing: Facing,
+    /// `art` facing the other way: hand-drawn if supplied, otherwise mirrored.
+    pub flipped: Cow<'static, str>,
+    /// Optional colour mask with the same layout as `art`.
+    pub mask: Option<&'static str>,
+    /// Mask matching `flipped`.
+    pub flipped_mask: Option<Cow<'static, str>>,
+    /// Which blank cells are see-through when drawn (fish use `auto_trans` in the original).
+    pub transparency: Transparency,
+}
+
+impl FishArt {
+    /// Measure `art`, guess its facing and precompute the mirrored variant.
+    pub fn new(art: &'static str) -> Self {
+        let (width, height) = measure_block(art);
+        Self {
+            art,
+            width,
+            height,
+            facing: if art_prefers_right(art) {
+                Facing::Right
+            } else {
+                Facing::Left
+            },
+            flipped: Cow::Owned(mirror_ascii_block(art, width)),
+            mask: None,
+            flipped_mask: None,
+            transparency: Transparency::Auto,
+        }
+    }
+
+    /// Override the transparency rule.
+    pub fn with_transparency(mut self, transparency: Transparency) -> Self {
+        self.transparency = transparency;
+        self
+    }
+
+    /// Declare the facing instead of relying on the heuristic.
+    pub fn with_facing(mut self, facing: Facing) -> Self {
+        self.facing = facing;
+        self
+    }
+
+    /// Use a hand-drawn opposite-facing version instead of the mirrored one.
+    pub fn with_flipped(mut self, flipped: &'static str) -> Self {
+        self.flipped = Cow::Borrowed(flipped);
+        self
+    }
+
+    /// Attach a colour mask; its mirrored counterpart is precomputed unless one was supplied.
+    pub fn with_mask(mut self, mask: &'static str) -> Self {
+        self.mask = Some(mask);
+        if self.flipped_mask.is_none() {
+            self.flipped_mask = Some(Cow::Owned(mirror_mask_block(mask, self.width)));
+        }
+        self
+    }
+
+    /// Use a hand-drawn mask for the flipped variant.
+    pub fn with_flipped_mask(mut self, mask: &'static str) -> Self {
+        self.flipped_mask = Some(Cow::Borrowed(mask));
+        self
+    }
+
+    /// Art to draw when facing `facing`.
+    pub fn art_facing(&self, facing: Facing) -> &str {
+        if facing == self.facing {
+            self.art
+        } else {
+            &self.flipped
+        }
+    }
+
+    /// Mask to draw when facing `facing`, if any.
+    pub fn mask_facing(&self, facing: Facing) -> Option<&str> {
+        if facing == self.facing {
+            self.mask
+        } else {
+            self.flipped_mask.as_deref()
+        }
+    }
+
+    /// Facing for a horizontal velocity; a stationary fish keeps its drawn facing.
+    pub fn facing_for_velocity(&self, vx: f32) -> Facing {
+        if vx > 0.0 {
+            Facing::Right
+        } else if vx < 0.0 {
+            Facing::Left
+        } else {
+            self.facing
+        }
+    }
 }
 
 /// A single moving fish instance in the aquarium.
 #[derive(Debug, Clone)]
 pub struct FishInstance {
-    /// Index into the assets slice.
+    /// Index into the assets slice (resolve stable IDs with `AssetRegistry::index_of`).
     pub fish_art_index: usize,
     /// Top-left position in character coordinates (float for smooth movement).
     pub position: (f32, f32),
@@ -35,15 +201,37 @@ pub struct FishInstance {
     pub velocity: (f32, f32),
 }
 
+/// A fish's detour from cruising, holding the velocity it had before.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cruise {
+    /// Scared (`scare_fish`): easing back to this speed and climb, keeping its new heading.
+    Darting((f32, f32)),
+    /// Chasing food (`update_food`): back to this velocity once no pellet is in sight.
+    Feeding((f32, f32)),
+}
+
+impl Cruise {
+    /// The velocity the fish goes back to.
+    pub fn velocity(&self) -> (f32, f32) {
+        match *self {
+            Cruise::Darting(velocity) | Cruise::Feeding(velocity) => velocity,
+        }
+    }
+}
+
 /// A bubble that rises towards the waterline.
 #[derive(Debug, Clone)]
 pub struct Bubble {
     pub position: (f32, f32),
     pub velocity: (f32, f32),
+    /// Ticks since release; bubbles grow with age.
+    pub age: u32,
+    /// Ticks left on the surface after popping; 0 while rising.
+    pub popped: u8,
 }
 
 /// A single seaweed stalk.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Seaweed {
     pub x: usize,
     pub height: usize,
@@ -82,10 +270,29 @@ pub struct AquariumEnvironment {
     pub water_phase: u8,
     /// Detected/generated set of seaweed stalks.
     pub seaweed: Vec<Seaweed>,
-    /// Whether to render the castle at bottom-right.
-    pub castle: bool,
+    /// Props resting in the tank; defaults to the castle at bottom-right.
+    pub decorations: Vec<Decoration>,
+    /// Optional terrain along the bottom; None keeps the classic empty floor.
+    pub seabed: Option<Seabed>,
+    /// Optional day/night cycle; None keeps the palette as-is, always daytime.
+    pub day_night: Option<DayNight>,
+    /// Surface weather; defaults to the classic fair waves.
+    pub weather: Weather,
+    /// Optional water current; None is still water.
+    pub current: Option<Current>,
+    /// Bubbles reaching the same cell merge into a bigger one.
+    pub merge_bubbles: bool,
+    /// Extra bubble sources on decorations, seaweed or cells (see `bubbles`).
+    pub emitters: Vec<BubbleEmitter>,
+    /// Keep the seaweed count in line with the width. Turned off once stalks are placed by
+    /// hand (edit mode, layouts) so deleted stalks stay deleted.
+    pub auto_seaweed: bool,
     /// Surface ships.
     pub ships: Vec<Ship>,
+    /// Fading trail behind the ships (see `wake`).
+    pub wake: Vec<WakeCell>,
+    /// Sinking food pellets (see `food`).
+    pub food: Vec<Pellet>,
     /// Underwater sharks.
     pub sharks: Vec<Shark>,
     /// Underwater whales.
@@ -96,6 +303,9 @@ pub struct AquariumEnvironment {
     pub next_whale_spawn: u64,
     /// Next eligible tick to spawn a school of fish
     pub next_school_spawn: u64,
+    /// Normal fish per cell that `resize_aquarium` keeps. `None` takes it from the Normal
+    /// fish present before each resize, so fish the host adds or removes stay counted.
+    pub fish_density: Option<f32>,
 }
 
 impl Default for AquariumEnvironment {
@@ -103,14 +313,24 @@ impl Default for AquariumEnvironment {
         Self {
             water_phase: 0,
             seaweed: Vec::new(),
-            castle: true,
+            decorations: vec![Decoration::castle()],
+            seabed: None,
+            day_night: None,
+            weather: Weather::default(),
+            current: None,
+            merge_bubbles: false,
+            emitters: Vec::new(),
+            auto_seaweed: true,
             ships: Vec::new(),
+            wake: Vec::new(),
+            food: Vec::new(),
             sharks: Vec::new(),
             whales: Vec::new(),
             next_ship_spawn: 0,
             next_shark_spawn: 0,
             next_whale_spawn: 0,
             next_school_spawn: 0,
+            fish_density: None,
         }
     }
 }
@@ -125,7 +345,7 @@ pub enum FishBehavior {
 }
 
 /// The aquarium state that the parent application owns and updates.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct AquariumState {
     /// Bounds of the aquarium in character cells (width, height).
     pub size: (usize, usize),
@@ -133,6 +353,9 @@ pub struct AquariumState {
     pub fishes: Vec<FishInstance>,
     /// Behavior associated with each fish (parallel to `fishes`). Defaults to Normal.
     pub fish_behaviors: Vec<FishBehavior>,
+    /// Why each fish is off its usual course, with the velocity it goes back to (parallel to
+    /// `fishes`). `None`, or a missing entry, while cruising.
+    pub fish_cruises: Vec<Option<Cruise>>,
     /// Rising bubbles.
     pub bubbles: Vec<Bubble>,
     /// Background/props animation state.
@@ -182,130 +405,314 @@ impl Default for AsciiquariumTheme {
 
 // Static environment art and helpers.
 
-const WATER_LINES: [&str; 4] = [
-    "~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~",
-    "^^^^ ^^^  ^^^   ^^^    ^^^^      ",
-    "^^^^      ^^^^     ^^^    ^^     ",
-    "^^      ^^^^      ^^^    ^^^^^^  ",
-];
-
-const CASTLE: &str = r#"
-               T~~
-               |
-              /^\
-             /   \
- _   _   _  /     \  _   _   _
-[ ]_[ ]_[ ]/ _   _ \[ ]_[ ]_[ ]
-|_=__-_ =_|_[ ]_[ ]_|_=-___-__|
- | _- =  | =_ = _    |= _=   |
- |= -[]  |- = _ =    |_-=_[] |
- | =_    |= - ___    | =_ =  |
- |=  []- |-  /| |\   |=_ =[] |
- |- =_   | =| | | |  |- = -  |
- |_______|__|_|_|_|__|_______|
-"#;
-
-// Ships (left/right)
-const SHIP_R: &str = r#"
-     |    |    |
-    )_)  )_)  )_)
-   )___))___))___)\
-  )____)____)_____)\\\
-_____|____|____|____\\\\\__
-\                   /
-"#;
-
-const SHIP_L: &str = r#"
-         |    |    |
-        (_(  (_(  (_(
-      /(___((___((___(
-    //(_____(____(____(
-__///____|____|____|_____
-    \                   /
-"#;
-
-// Sharks (left/right) - simplified large ASCII
-const SHARK_R: &str = r#"
-                              __
-                             ( `\
-  ,??????????????????????????)   `\
-;' `.????????????????????????(     `\__
- ;   `.?????????????__..---''          `~~~~-._
-  `.   `.____...--''                       (b  `--._
-    >                     _.-'      .((      ._     )
-  .`.-`--...__         .-'     -.___.....-(|/|/|/|/'
- ;.'?????????`. ...----`.___.',,,_______......---'
- '???????????'-'
-"#;
-
-const SHARK_L: &str = r#"
-                     __
-                    /' )
-                  /'   (??????????????????????????,
-              __/'     )????????????????????????.' `;
-      _.-~~~~'          ``---..__?????????????.'   ;
- _.--'  b)                       ``--...____.'   .'
-(     _.      )).      `-._                     <
- `\|\|\|\|)-.....___.-     `-.         __...--'-.'.
-   `---......_______,,,`.___.'----... .'?????????`.;
-                                     `-`???????????`
-"#;
-
-// Whales (left/right)
-const WHALE_R: &str = r#"
-        .-----:
-      .'       `.
-,????/       (o) \
-\`._/          ,__)
-"#;
-
-const WHALE_L: &str = r#"
-    :-----.
-  .'       `.
- / (o)       \????,
-(__,          \_.'/
-"#;
-
-// Water spout frames (small)
-const SPOUT_FRAMES: [&str; 7] = [
-    r#"
-
-   :
-"#,
-    r#"
-   :
-   :
-"#,
-    r#"
-  . .
-  -:-
-   :
-"#,
-    r#"
-  . .
- .-:-.
-   :
-"#,
-    r#"
-  . .
-'.-:-.`
-'  :  '
-"#,
-    r#"
- .- -.
-;  :  ;
-"#,
-    r#"
-
-;     ;
-"#,
-];
+// Creature and prop art comes from the generated module (extracted from the original).
+const WATER_LINES: [&str; 4] = generated::WATER_LINE_SEGMENTS;
+const SHIP_R: &str = generated::SHIP_RIGHT.frames[0];
+const SHIP_L: &str = generated::SHIP_LEFT.frames[0];
+const SHARK_R: &str = generated::SHARK_RIGHT.frames[0];
+const SHARK_L: &str = generated::SHARK_LEFT.frames[0];
+const WHALE_R: &str = generated::WHALE_RIGHT.frames[0];
+const WHALE_L: &str = generated::WHALE_LEFT.frames[0];
+const SPOUT_FRAMES: &[&str] = generated::WATER_SPOUT.frames;
+/// Like the original, a whale shows five frames without a spout before each spout.
+const SPOUT_REST_FRAMES: usize = 5;
+/// Ticks per whale animation frame.
+const SPOUT_FRAME_TICKS: u64 = 12;
+const SHIP_TRANSPARENCY: Transparency = generated::SHIP_RIGHT.transparency;
+const SHARK_TRANSPARENCY: Transparency = generated::SHARK_RIGHT.transparency;
+// The original composites the spout into the whale frames, so both share the whale's rule.
+const WHALE_TRANSPARENCY: Transparency = generated::WHALE_RIGHT.transparency;
+
+/// Something drawn in the tank that hit-testing can report. Indices refer to the matching
+/// `Vec` in `AquariumState` / `AquariumEnvironment` at the time of the query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntityRef {
+    Fish(usize),
+    Ship(usize),
+    Shark(usize),
+    Whale(usize),
+    Seaweed(usize),
+    Decoration(usize),
+}
+
+/// A sprite at its on-screen position for the current tick.
+#[derive(Debug, Clone)]
+pub struct PlacedSprite<'a> {
+    pub entity: EntityRef,
+    /// Top-left cell (may be off-grid).
+    pub origin: (isize, isize),
+    pub art: &'a str,
+    pub transparency: Transparency,
+}
+
+/// Subtle vertical bob (amplitude 1) with a slow phase, desynced by column.
+fn bob(tick: u64, period: u64, x0: isize) -> isize {
+    let phase_u = (tick / period) % 2;
+    let phase_i = (x0.rem_euclid(2)) as u64;
+    if (phase_u + phase_i) % 2 == 0 {
+        0
+    } else {
+        1
+    }
+}
+
+/// Where ship `index` is drawn this tick.
+pub fn ship_sprite(state: &AquariumState, index: usize, ship: &Ship) -> PlacedSprite<'static> {
+    let x0 = ship.x.floor() as isize;
+    PlacedSprite {
+        entity: EntityRef::Ship(index),
+        origin: (
+            x0,
+            ship.y as isize + ship_bob(state.env.weather.kind, state.tick, x0),
+        ),
+        art: if ship.vx >= 0.0 { SHIP_R } else { SHIP_L },
+        transparency: SHIP_TRANSPARENCY,
+    }
+}
+
+/// Where decoration `index` is drawn this tick (current animation frame).
+pub fn decoration_sprite<'a>(
+    state: &AquariumState,
+    index: usize,
+    decoration: &'a Decoration,
+) -> PlacedSprite<'a> {
+    let frame = decoration.frame(state.tick);
+    PlacedSprite {
+        entity: EntityRef::Decoration(index),
+        origin: decoration.origin(state.size, state.env.seabed.as_ref()),
+        art: decoration.kind.frame_art(frame),
+        transparency: decoration.kind.transparency(),
+    }
+}
+
+/// Sprites of the decorations behind (`front == false`) or in front of fish, in depth order.
+pub fn decoration_sprites(state: &AquariumState, front: bool) -> Vec<PlacedSprite<'_>> {
+    let mut out: Vec<(u8, PlacedSprite<'_>)> = state
+        .env
+        .decorations
+        .iter()
+        .enumerate()
+        .filter(|(_, d)| d.in_front() == front)
+        .map(|(i, d)| (d.depth, decoration_sprite(state, i, d)))
+        .collect();
+    out.sort_by_key(|(depth, _)| *depth);
+    out.into_iter().map(|(_, sprite)| sprite).collect()
+}
+
+/// Whale `index`'s body and, while it is spouting, its water spout above the blowhole.
+/// The spout rests for `SPOUT_REST_FRAMES` frames, then plays once, as in the original.
+pub fn whale_sprites(
+    state: &AquariumState,
+    index: usize,
+    whale: &Whale,
+) -> Vec<PlacedSprite<'static>> {
+    let x0 = whale.x.floor() as isize;
+    let y0 = whale.y as isize + bob(state.tick, 96, x0);
+    let right = whale.vx >= 0.0;
+    let mut out = vec![PlacedSprite {
+        entity: EntityRef::Whale(index),
+        origin: (x0, y0),
+        art: if right { WHALE_R } else { WHALE_L },
+        transparency: WHALE_TRANSPARENCY,
+    }];
+    let cycle = SPOUT_REST_FRAMES + SPOUT_FRAMES.len();
+    let frame = (state.tick / SPOUT_FRAME_TICKS) as usize % cycle;
+    if let Some(spout) = frame
+        .checked_sub(SPOUT_REST_FRAMES)
+        .and_then(|i| SPOUT_FRAMES.get(i))
+    {
+        // The spout anchor comes with the whale art (`$spout_align` in the original).
+        let (align_r, align_l) = generated::WHALE_SPOUT_ALIGN;
+        let align = if right { align_r } else { align_l } as isize;
+        out.push(PlacedSprite {
+            entity: EntityRef::Whale(index),
+            origin: (x0 + align, y0 - measure_block(spout).1 as isize),
+            art: spout,
+            transparency: WHALE_TRANSPARENCY,
+        });
+    }
+    out
+}
+
+/// Where shark `index` is drawn this tick.
+pub fn shark_sprite(state: &AquariumState, index: usize, shark: &Shark) -> PlacedSprite<'static> {
+    let x0 = shark.x.floor() as isize;
+    PlacedSprite {
+        entity: EntityRef::Shark(index),
+        origin: (x0, shark.y as isize + bob(state.tick, 80, x0)),
+        art: if shark.vx >= 0.0 { SHARK_R } else { SHARK_L },
+        transparency: SHARK_TRANSPARENCY,
+    }
+}
+
+/// Where fish `index` is drawn, facing its direction of travel. None for unknown assets.
+pub fn fish_sprite<'a>(
+    assets: &'a [FishArt],
+    index: usize,
+    fish: &FishInstance,
+) -> Option<PlacedSprite<'a>> {
+    let art = assets.get(fish.fish_art_index)?;
+    Some(PlacedSprite {
+        entity: EntityRef::Fish(index),
+        origin: (
+            fish.position.0.floor() as isize,
+            fish.position.1.floor() as isize,
+        ),
+        art: art.art_facing(art.facing_for_velocity(fish.velocity.0)),
+        transparency: art.transparency,
+    })
+}
+
+/// Every entity sprite in draw order (bottom first). Seaweed, water and bubbles are not
+/// sprites and are left out.
+pub fn placed_sprites<'a>(
+    state: &'a AquariumState,
+    assets: &'a [FishArt],
+) -> Vec<PlacedSprite<'a>> {
+    let mut out = Vec::new();
+    for (i, ship) in state.env.ships.iter().enumerate() {
+        out.push(ship_sprite(state, i, ship));
+    }
+    out.extend(decoration_sprites(state, false));
+    for (i, whale) in state.env.whales.iter().enumerate() {
+        out.extend(whale_sprites(state, i, whale));
+    }
+    for (i, shark) in state.env.sharks.iter().enumerate() {
+        out.push(shark_sprite(state, i, shark));
+    }
+    for (i, fish) in state.fishes.iter().enumerate() {
+        out.extend(fish_sprite(assets, i, fish));
+    }
+    out.extend(decoration_sprites(state, true));
+    out
+}
+
+/// Whether `sprite` draws an opaque glyph on `cell` (wide glyphs cover both their cells).
+pub fn sprite_covers(sprite: &PlacedSprite<'_>, cell: (usize, usize)) -> bool {
+    let dy = cell.1 as isize - sprite.origin.1;
+    let dx = cell.0 as isize - sprite.origin.0;
+    if dy < 0 || dx < 0 {
+        return false;
+    }
+    let Some(line) = sprite.art.lines().nth(dy as usize) else {
+        return false;
+    };
+    let mut leading = true;
+    for (x, g) in cells_of(line) {
+        leading &= g.chars().all(char::is_whitespace);
+        let end = x + grapheme_width(g);
+        if (dx as usize) < x {
+            return false;
+        }
+        if (dx as usize) < end {
+            return !is_transparent(g, leading, sprite.transparency);
+        }
+    }
+    false
+}
+
+/// First row below open water under columns `x0..x0 + width`: the top of the seabed there,
+/// or the tank height without one.
+pub fn floor_row(state: &AquariumState, x0: isize, width: usize) -> usize {
+    floor_under(state.env.seabed.as_ref(), x0, width, state.size)
+}
+
+/// Row of a seaweed stalk's top cell: it grows `height` rows up from the floor under it.
+pub fn seaweed_top(state: &AquariumState, stalk: &Seaweed) -> usize {
+    floor_row(state, stalk.x as isize, 2).saturating_sub(stalk.height)
+}
+
+/// Cells drawn for seaweed stalk `index` this tick, as (x, y, glyph), clipped to the tank.
+/// Stalks sway -1/0/+1 with `water_phase` plus their own phase and lean with the current;
+/// every third stalk is doubled.
+pub fn seaweed_cells(
+    state: &AquariumState,
+    index: usize,
+    stalk: &Seaweed,
+) -> Vec<(isize, isize, &'static str)> {
+    let (w, h) = state.size;
+    let mut out = Vec::new();
+    let base_y = seaweed_top(state, stalk);
+    // sway: -1, 0, +1 cycling at a slow rate
+    let phase = (state.env.water_phase.wrapping_add(stalk.sway_phase)) / 8;
+    let sway = match phase % 3 {
+        0 => -1isize,
+        1 => 0isize,
+        _ => 1isize,
+    };
+    // Alternating '(' and ')' vertically.
+    for dy in 0..stalk.height {
+        let y = base_y + dy;
+        if y >= h {
+            continue;
+        }
+        let left = dy % 2 == 0;
+        let lean = seaweed_lean(state, stalk.x, base_y, stalk.height, dy);
+        let x = stalk.x as isize + if left { 0 } else { 1 } + sway + lean;
+        if x < 0 || (x as usize) >= w {
+            continue;
+        }
+        out.push((x, y as isize, if left { "(" } else { ")" }));
+    }
+    // Slight horizontal spread for some stalks to avoid uniformity.
+    if index % 3 == 0 {
+        let x2 = (stalk.x + 1).min(w.saturating_sub(1));
+        for dy in 1..stalk.height {
+            let y = base_y + dy;
+            if y >= h {
+                continue;
+            }
+            let x = x2 as isize + sway + seaweed_lean(state, stalk.x, base_y, stalk.height, dy);
+            if x < 0 || (x as usize) >= w {
+                continue;
+            }
+            out.push((x, y as isize, if dy % 2 == 0 { "(" } else { ")" }));
+        }
+    }
+    out
+}
+
+fn is_transparent(g: &str, leading: bool, transparency: Transparency) -> bool {
+    match transparency {
+        _ if g == "?" => true,
+        Transparency::Auto => leading,
+        Transparency::Opaque => false,
+        Transparency::Spaces => g == " ",
+    }
+}
+
+fn blit_sprite<'a>(grid: &mut CellGrid<'a>, sprite: &PlacedSprite<'a>) {
+    blit(grid, sprite.origin, sprite.art, sprite.transparency);
+}
+
+/// Draw `art` with its top-left corner at `origin`, clipping to the grid and leaving
+/// transparent cells untouched. Wide glyphs advance two cells.
+fn blit<'a>(
+    grid: &mut CellGrid<'a>,
+    origin: (isize, isize),
+    art: &'a str,
+    transparency: Transparency,
+) {
+    for (dy, line) in art.lines().enumerate() {
+        let y = origin.1 + dy as isize;
+        if y < 0 || y >= grid.height as isize {
+            continue;
+        }
+        let mut leading = true;
+        for (dx, g) in cells_of(line) {
+            leading &= g.chars().all(char::is_whitespace);
+            if !is_transparent(g, leading, transparency) {
+                grid.put(origin.0 + dx as isize, y, g);
+            }
+        }
+    }
+}
 
 fn measure_block(art: &str) -> (usize, usize) {
     let mut w = 0usize;
     let mut h = 0usize;
     for line in art.lines() {
-        w = w.max(line.chars().count());
+        w = w.max(display_width(line));
         h += 1;
     }
     (w.max(1), h.max(1))
@@ -331,9 +738,44 @@ fn mirror_char(c: char) -> char {
     }
 }
 
-/// Mirror an ASCII line: reverse order and swap mirrored pairs.
-fn mirror_ascii_line(s: &str) -> String {
-    s.chars().rev().map(mirror_char).collect()
+/// Mirror an ASCII block: pad each line to `width`, reverse it and swap mirrored pairs,
+/// so lines of different lengths stay aligned.
+fn mirror_ascii_block(art: &str, width: usize) -> String {
+    mirror_block_with(art, width, mirror_char)
+}
+
+/// Mirror a colour mask: same alignment as `mirror_ascii_block`, glyphs unchanged.
+fn mirror_mask_block(mask: &str, width: usize) -> String {
+    mirror_block_with(mask, width, |c| c)
+}
+
+/// Works on grapheme clusters, so wide glyphs and combining marks survive mirroring;
+/// `map` only applies to single-char clusters.
+fn mirror_block_with(art: &str, width: usize, map: impl Fn(char) -> char) -> String {
+    let mut out = String::with_capacity(art.len() + width);
+    if art.starts_with('\n') {
+        out.push('\n');
+    }
+    let mut first = true;
+    for line in art.lines().skip(usize::from(art.starts_with('\n'))) {
+        if !first {
+            out.push('\n');
+        }
+        first = false;
+        let pad = width.saturating_sub(display_width(line));
+        let mut clusters: Vec<&str> = graphemes(line).collect();
+        clusters.extend(std::iter::repeat(" ").take(pad));
+        let mut mirrored = String::with_capacity(line.len() + pad);
+        for g in clusters.iter().rev() {
+            let mut chars = g.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => mirrored.push(map(c)),
+                _ => mirrored.push_str(g),
+            }
+        }
+        out.push_str(mirrored.trim_end());
+    }
+    out
 }
 
 /// Heuristic: decide if the art prefers facing right.
@@ -387,22 +829,46 @@ fn art_prefers_right(art: &str) -> bool {
     score >= 0
 }
 
+/// Keep the seaweed stalks `keep` accepts (called with each stalk's old index). Bubble
+/// emitters on a dropped stalk are removed and the rest follow their stalk's new index.
+fn retain_seaweed(
+    env: &mut AquariumEnvironment,
+    mut keep: impl FnMut(usize, &mut Seaweed) -> bool,
+) {
+    let mut removed = Vec::new();
+    let mut index = 0;
+    env.seaweed.retain_mut(|stalk| {
+        let kept = keep(index, stalk);
+        if !kept {
+            removed.push(index);
+        }
+        index += 1;
+        kept
+    });
+    // Highest first, so the indices still to detach stay valid.
+    for &i in removed.iter().rev() {
+        detach_emitters(&mut env.emitters, EntityRef::Seaweed(i));
+    }
+}
+
 fn ensure_environment_initialized(state: &mut AquariumState) {
-    // Generate seaweed based on width if none present or if size changed significantly.
+    // Keep the seaweed count in line with the width: drop extras, seed any missing stalks.
     let (w, h) = state.size;
     if w == 0 || h == 0 {
-        state.env.seaweed.clear();
+        retain_seaweed(&mut state.env, |_, _| false);
+        return;
+    }
+    if !state.env.auto_seaweed {
         return;
     }
     let target_count = (w / 15).max(1);
-    if state.env.seaweed.len() != target_count {
-        state.env.seaweed.clear();
-
+    retain_seaweed(&mut state.env, |i, _| i < target_count);
+    if state.env.seaweed.len() < target_count {
         // Deterministic seeded placement based on size (no external RNG).
         let mut s: u64 = 0x9E37_79B9_7F4A_7C15u64 ^ ((w as u64) << 32) ^ (h as u64);
 
-        let mut xs: Vec<usize> = Vec::with_capacity(target_count);
-        for _ in 0..target_count {
+        let mut xs: Vec<usize> = state.env.seaweed.iter().map(|st| st.x).collect();
+        while state.env.seaweed.len() < target_count {
             // Advance seed and choose x in [1, w-2] when possible
             s = s.wrapping_mul(6364136223846793005).wrapping_add(1);
             let mut x = 1 + (s as usize % w.saturating_sub(2).max(1));
@@ -436,9 +902,20 @@ fn ensure_environment_initialized(state: &mut AquariumState) {
     }
 }
 
+/// Row a shark swims at: a consistent depth under the waterlines.
+fn shark_depth(height: usize) -> usize {
+    let (_, sh) = measure_block(SHARK_R);
+    height.saturating_sub(sh + 3).max(9)
+}
+
+/// Row a whale swims at: mid-depth.
+fn whale_depth(height: usize) -> usize {
+    (height / 3).max(6)
+}
+
 /// Update the aquarium by one tick with simple wall-bounce physics and environment.
 pub fn update_aquarium(state: &mut AquariumState, assets: &[FishArt]) {
-    let (aw, ah) = (state.size.0 as f32, state.size.1 as f32);
+    let aw = state.size.0 as f32;
     let dt: f32 = CLASSIC_DT;
     let fish_speed_mult: f32 = CLASSIC_FISH_SPEED_MULT;
 
@@ -453,6 +930,10 @@ pub fn update_aquarium(state: &mut AquariumState, assets: &[FishArt]) {
     } else if state.fish_behaviors.len() > state.fishes.len() {
         state.fish_behaviors.truncate(state.fishes.len());
     }
+    state.fish_cruises.resize(state.fishes.len(), None);
+
+    // Food sinks and steers nearby fish before they move.
+    update_food(state, assets);
 
     // Integrate fish and handle bounce.
 
@@ -470,13 +951,12 @@ pub fn update_aquarium(state: &mut AquariumState, assets: &[FishArt]) {
         } else {
             (state.size.0 as f32 + sw as f32, -6.0)
         };
-        state.env.ships.push(Ship { x, y: 0, vx });
+        // Row 1 keeps the hull riding on the upper water rows.
+        state.env.ships.push(Ship { x, y: 1, vx });
     }
     if state.env.sharks.is_empty() && state.tick >= state.env.next_shark_spawn {
         // Place shark at a consistent depth under waterlines.
-        let (_, sh) = measure_block(SHARK_R);
-        let base = 9;
-        let y = state.size.1.saturating_sub(sh + 3).max(base);
+        let y = shark_depth(state.size.1);
         let right = (state.tick / 1200) % 2 == 0;
         let (sw, _) = if right {
             measure_block(SHARK_R)
@@ -492,7 +972,7 @@ pub fn update_aquarium(state: &mut AquariumState, assets: &[FishArt]) {
     }
     if state.env.whales.is_empty() && state.tick >= state.env.next_whale_spawn {
         // Mid-depth whale.
-        let y = (state.size.1 / 3).max(6);
+        let y = whale_depth(state.size.1);
         let right = (state.tick / 1500) % 2 == 0;
         let (ww, _) = if right {
             measure_block(WHALE_R)
@@ -508,7 +988,13 @@ pub fn update_aquarium(state: &mut AquariumState, assets: &[FishArt]) {
     }
     // Spawn a deterministic school of fish at intervals
     if state.tick >= state.env.next_school_spawn && !assets.is_empty() {
-        let assets_len = assets.len();
+        // Schools are made of small fish; fall back to every asset if none qualify.
+        let mut candidates: Vec<usize> = (0..assets.len())
+            .filter(|&i| is_small_fish(&assets[i]))
+            .collect();
+        if candidates.is_empty() {
+            candidates = (0..assets.len()).collect();
+        }
         let dir_right = (state.tick / 2400) % 2 == 0;
         let count = 5 + ((state.tick as usize / 7) % 6); // 5..10
         let mid = (state.size.1 / 2).max(6);
@@ -521,7 +1007,7 @@ pub fn update_aquarium(state: &mut AquariumState, assets: &[FishArt]) {
         };
         // Stagger fish along entry to keep tight school
         for i in 0..count {
-            let art_idx = (i + ((state.tick as usize / 5) % assets_len)) % assets_len;
+            let art_idx = pick_seeded(&candidates, state.tick / 5 + i as u64).unwrap_or_default();
             let xi = start_x - (i as f32) * 4.0;
             state.fishes.push(FishInstance {
                 fish_art_index: art_idx,
@@ -529,15 +1015,30 @@ pub fn update_aquarium(state: &mut AquariumState, assets: &[FishArt]) {
                 velocity: (speed, 0.0),
             });
             state.fish_behaviors.push(FishBehavior::Transit);
+            state.fish_cruises.push(None);
         }
         state.env.next_school_spawn = state.tick + 1800; // ~60s at 30 fps
     }
     // Update fish with behavior-aware logic (Transit vs Normal).
     let mut kept_fishes: Vec<FishInstance> = Vec::with_capacity(state.fishes.len());
     let mut kept_behaviors: Vec<FishBehavior> = Vec::with_capacity(state.fish_behaviors.len());
+    let mut kept_cruises: Vec<Option<Cruise>> = Vec::with_capacity(state.fish_cruises.len());
     for i in 0..state.fishes.len() {
         let mut fish = state.fishes[i].clone();
         let behavior = *state.fish_behaviors.get(i).unwrap_or(&FishBehavior::Normal);
+        let mut cruise = state.fish_cruises.get(i).copied().flatten();
+
+        // Darting (scared) fish ease back to their old speed, keeping their new heading.
+        if let Some(Cruise::Darting((vx, vy))) = cruise {
+            let speed = (fish.velocity.0.abs() * DART_DAMPING).max(vx.abs());
+            fish.velocity.1 = vy + (fish.velocity.1 - vy) * DART_DAMPING;
+            if speed - vx.abs() < DART_SETTLE {
+                fish.velocity = (vx.abs().copysign(fish.velocity.0), vy);
+                cruise = None;
+            } else {
+                fish.velocity.0 = speed.copysign(fish.velocity.0);
+            }
+        }
 
         fish.position.0 += fish.velocity.0 * dt * fish_speed_mult;
         fish.position.1 += fish.velocity.1 * dt * fish_speed_mult;
@@ -552,6 +1053,14 @@ pub fn update_aquarium(state: &mut AquariumState, assets: &[FishArt]) {
             .map(|a| (a.width as f32, a.height as f32))
             .unwrap_or((1.0, 1.0));
 
+        // Small fish are weak swimmers and drift with the current.
+        if assets.get(fish.fish_art_index).is_some_and(is_small_fish) {
+            let centre = (fish.position.0 + fw / 2.0, fish.position.1 + fh / 2.0);
+            let (cx, cy) = current_at(state, centre);
+            fish.position.0 += cx * SMALL_FISH_DRIFT * dt;
+            fish.position.1 += cy * SMALL_FISH_DRIFT * dt;
+        }
+
         if behavior == FishBehavior::Transit {
             // Despawn transit fish once fully off-screen.
             let off_right = fish.position.0 > aw;
@@ -561,11 +1070,13 @@ pub fn update_aquarium(state: &mut AquariumState, assets: &[FishArt]) {
             } else {
                 kept_fishes.push(fish);
                 kept_behaviors.push(behavior);
+                kept_cruises.push(cruise);
             }
             continue;
         }
 
-        // Normal bounce behavior.
+        // Normal bounce behavior; the bottom is the seabed under the fish, if any.
+        let floor = floor_row(state, fish.position.0.floor() as isize, fw as usize) as f32;
         if fish.position.0 < 0.0 {
             fish.position.0 = 0.0;
             fish.velocity.0 = fish.velocity.0.abs();
@@ -598,8 +1109,8 @@ pub fn update_aquarium(state: &mut AquariumState, assets: &[FishArt]) {
             if (flip_seed & 0x0F) == 0 {
                 fish.velocity.0 = -fish.velocity.0;
             }
-        } else if fish.position.1 + fh > ah {
-            fish.position.1 = (ah - fh).max(0.0);
+        } else if fish.position.1 + fh > floor {
+            fish.position.1 = (floor - fh).max(0.0);
             fish.velocity.1 = -fish.velocity.1.abs();
             // Small deterministic chance to flip horizontal direction on wall bounce for natural variance.
             let flip_seed =
@@ -612,9 +1123,11 @@ pub fn update_aquarium(state: &mut AquariumState, assets: &[FishArt]) {
 
         kept_fishes.push(fish);
         kept_behaviors.push(behavior);
+        kept_cruises.push(cruise);
     }
     state.fishes = kept_fishes;
     state.fish_behaviors = kept_behaviors;
+    state.fish_cruises = kept_cruises;
 
     // Occasionally emit bubbles from fish mouths, deterministically based on tick.
     // Emit every 24 ticks per fish to avoid randomness in the core crate.
@@ -632,23 +1145,23 @@ pub fn update_aquarium(state: &mut AquariumState, assets: &[FishArt]) {
             } else {
                 fish.position.0 - 1.0
             };
-            state.bubbles.push(Bubble {
-                position: (bx, mid_y),
-                velocity: (0.0, -3.0),
-            });
+            state.bubbles.push(Bubble::new((bx, mid_y), (0.0, -3.0)));
         }
     }
 
-    // Update bubbles (rise) and cull above waterline (y < 0).
-    let mut kept = Vec::with_capacity(state.bubbles.len());
-    for mut b in state.bubbles.drain(..) {
-        b.position.0 += b.velocity.0 * dt;
-        b.position.1 += b.velocity.1 * dt;
-        if b.position.1 >= 0.0 {
-            kept.push(b);
+    emit_bubbles(state);
+
+    // Animated decorations (an opening chest) release bubbles from their top centre.
+    for decoration in &state.env.decorations {
+        if decoration.releases_bubble(state.tick) {
+            let (x, y) = decoration.origin(state.size, state.env.seabed.as_ref());
+            let (dw, _) = decoration.kind.size();
+            state.bubbles.push(Bubble::new(
+                (x as f32 + (dw / 2) as f32, y as f32),
+                (0.0, -3.0),
+            ));
         }
     }
-    state.bubbles = kept;
 
     // Move ships and despawn when fully off-screen. Schedule next spawn.
     let mut next_ships = Vec::with_capacity(state.env.ships.len());
@@ -710,35 +1223,283 @@ pub fn update_aquarium(state: &mut AquariumState, assets: &[FishArt]) {
     }
     state.env.whales = next_whales;
 
+    update_weather(state);
+    update_wake(state);
+
     // Advance environment phases.
     if state.tick % 4 == 0 {
         state.env.water_phase = state.env.water_phase.wrapping_add(1);
     }
+
+    // Bubbles rise and pop against the surface as it will be drawn this frame.
+    update_bubbles(state, dt);
     state.tick = state.tick.wrapping_add(1);
 }
 
+/// Scare the fish around `cell`: each fish whose centre is within `SCARE_RADIUS` darts
+/// away from it at high speed, then eases back to the speed and climb it had before over the
+/// next few seconds (`AquariumState::fish_cruises`). `cell` is a point in cell units, so the
+/// middle of cell `(x, y)` is `(x + 0.5, y + 0.5)`.
+/// Returns how many fish were scared.
+pub fn scare_fish(state: &mut AquariumState, assets: &[FishArt], cell: (f32, f32)) -> usize {
+    let mut scared = 0;
+    state.fish_cruises.resize(state.fishes.len(), None);
+    for (fish, cruise) in state.fishes.iter_mut().zip(&mut state.fish_cruises) {
+        let (fw, fh) = assets
+            .get(fish.fish_art_index)
+            .map(|a| (a.width as f32, a.height as f32))
+            .unwrap_or((1.0, 1.0));
+        let dx = fish.position.0 + fw * 0.5 - cell.0;
+        // Rows are about twice as tall as columns are wide.
+        let dy = (fish.position.1 + fh * 0.5 - cell.1) * 2.0;
+        if dx * dx + dy * dy > SCARE_RADIUS * SCARE_RADIUS {
+            continue;
+        }
+        let away = if dx >= 0.0 { 1.0 } else { -1.0 };
+        // A fish scared again while darting (or while feeding) keeps its cruising velocity.
+        let calm = cruise.map_or(fish.velocity, |c| c.velocity());
+        *cruise = Some(Cruise::Darting(calm));
+        fish.velocity.0 = away * SCARE_SPEED;
+        fish.velocity.1 = if dy.abs() < 0.5 {
+            0.0
+        } else {
+            dy.signum() * CRUISE_MAX_VY * 4.0
+        };
+        scared += 1;
+    }
+    scared
+}
+
+/// Change `state.size` and bring everything back inside the new bounds:
+/// - Normal fish are clamped into the tank; transit fish keep their lane. Neither stays below
+///   the floor (the seabed under the fish, if any).
+/// - Seaweed stalks past the right edge are dropped (with their bubble emitters) and the rest
+///   are capped at the new height.
+/// - Visitors deeper than the tank move up; left-moving ones parked far past the new right
+///   edge are brought to it so they enter straight away.
+/// - Bubbles outside the tank are culled.
+///
+/// Returns false (and changes nothing) if the size is unchanged.
+pub fn set_aquarium_size(
+    state: &mut AquariumState,
+    assets: &[FishArt],
+    size: (usize, usize),
+) -> bool {
+    if state.size == size {
+        return false;
+    }
+    state.size = size;
+    let (w, h) = (size.0 as f32, size.1 as f32);
+
+    for (i, fish) in state.fishes.iter_mut().enumerate() {
+        let (fw, fh) = assets
+            .get(fish.fish_art_index)
+            .map(|a| (a.width, a.height))
+            .unwrap_or((1, 1));
+        let transit = state.fish_behaviors.get(i) == Some(&FishBehavior::Transit);
+        if !transit {
+            fish.position.0 = fish.position.0.min(w - fw as f32).max(0.0);
+        }
+        // Not into the sand: the floor is the seabed under the fish, if any.
+        let x0 = fish.position.0.floor() as isize;
+        let floor = floor_under(state.env.seabed.as_ref(), x0, fw, size) as f32;
+        fish.position.1 = fish.position.1.min(floor - fh as f32).max(0.0);
+    }
+
+    retain_seaweed(&mut state.env, |_, s| s.x + 1 < size.0);
+    for stalk in &mut state.env.seaweed {
+        stalk.height = stalk.height.min(size.1);
+    }
+
+    for decoration in &mut state.env.decorations {
+        let (dw, dh) = decoration.kind.size();
+        let (x, y) = decoration.position;
+        decoration.position = (
+            x.min(size.0.saturating_sub(dw)),
+            y.min(size.1.saturating_sub(dh)),
+        );
+    }
+
+    let fit_y = |y: usize, art: &str| y.min(size.1.saturating_sub(measure_block(art).1));
+    let fit_x = |x: f32, vx: f32, art: &str| {
+        if vx < 0.0 {
+            x.min(w + measure_block(art).0 as f32)
+        } else {
+            x
+        }
+    };
+    for ship in &mut state.env.ships {
+        ship.x = fit_x(ship.x, ship.vx, SHIP_L);
+    }
+    for shark in &mut state.env.sharks {
+        shark.x = fit_x(shark.x, shark.vx, SHARK_L);
+        shark.y = fit_y(shark.y, SHARK_R);
+    }
+    for whale in &mut state.env.whales {
+        whale.x = fit_x(whale.x, whale.vx, WHALE_L);
+        whale.y = fit_y(whale.y, WHALE_R);
+    }
+
+    state
+        .bubbles
+        .retain(|b| b.position.0 >= 0.0 && b.position.0 < w && b.position.1 < h);
+    true
+}
+
+/// Resize the tank, keeping the scene's layout and density:
+/// - Fish, bubbles and visitors move proportionally to the new size, then anything still
+///   outside is clamped or culled (see `set_aquarium_size`).
+/// - Seaweed stalks keep their height and sway and move proportionally; stalks are only added
+///   or dropped to match the new width.
+/// - Shark and whale depths are re-derived from the new height.
+/// - Normal fish are added or removed (seeded, newest first) to keep fish per cell constant.
+/// - A size with a zero dimension keeps every fish; the population is left for the next
+///   real size rather than culled to nothing.
+///
+/// Returns false (and changes nothing) if the size is unchanged.
+pub fn resize_aquarium(
+    state: &mut AquariumState,
+    assets: &[FishArt],
+    new_size: (usize, usize),
+) -> bool {
+    let old = state.size;
+    if old == new_size {
+        return false;
+    }
+    let normal = |state: &AquariumState| {
+        (0..state.fishes.len())
+            .filter(|&i| state.fish_behaviors.get(i) != Some(&FishBehavior::Transit))
+            .count()
+    };
+    let mut density = state.env.fish_density;
+    let has_area = |size: (usize, usize)| size.0 > 0 && size.1 > 0;
+    if has_area(old) && has_area(new_size) {
+        let sx = new_size.0 as f32 / old.0 as f32;
+        let sy = new_size.1 as f32 / old.1 as f32;
+        if density.is_none() {
+            density = Some(normal(state) as f32 / (old.0 * old.1) as f32);
+        }
+        for fish in &mut state.fishes {
+            fish.position = (fish.position.0 * sx, fish.position.1 * sy);
+        }
+        for b in &mut state.bubbles {
+            b.position = (b.position.0 * sx, b.position.1 * sy);
+        }
+        let mut taken: Vec<usize> = Vec::with_capacity(state.env.seaweed.len());
+        retain_seaweed(&mut state.env, |_, stalk| {
+            stalk.x = (stalk.x as f32 * sx).round() as usize;
+            let fresh = !taken.contains(&stalk.x);
+            taken.push(stalk.x);
+            fresh
+        });
+        for decoration in &mut state.env.decorations {
+            let (x, y) = decoration.position;
+            decoration.position = match decoration.anchor {
+                DecorationAnchor::Bottom => ((x as f32 * sx).round() as usize, y),
+                DecorationAnchor::Free => (
+                    (x as f32 * sx).round() as usize,
+                    (y as f32 * sy).round() as usize,
+                ),
+                DecorationAnchor::Left | DecorationAnchor::Right => (x, y),
+            };
+        }
+        for ship in &mut state.env.ships {
+            ship.x *= sx;
+        }
+        for shark in &mut state.env.sharks {
+            shark.x *= sx;
+            shark.y = shark_depth(new_size.1);
+        }
+        for whale in &mut state.env.whales {
+            whale.x *= sx;
+            whale.y = whale_depth(new_size.1);
+        }
+    }
+    set_aquarium_size(state, assets, new_size);
+    ensure_environment_initialized(state);
+
+    // Re-seed Normal fish for the new area.
+    let Some(density) = density.filter(|_| has_area(new_size)) else {
+        return true;
+    };
+    let target = (density * (new_size.0 * new_size.1) as f32).round() as usize;
+    state
+        .fish_behaviors
+        .resize(state.fishes.len(), FishBehavior::Normal);
+    state.fish_cruises.resize(state.fishes.len(), None);
+    let mut count = normal(state);
+    while count > target {
+        let Some(i) = (0..state.fishes.len())
+            .rev()
+            .find(|&i| state.fish_behaviors.get(i) != Some(&FishBehavior::Transit))
+        else {
+            break;
+        };
+        state.fishes.remove(i);
+        state.fish_behaviors.remove(i);
+        state.fish_cruises.remove(i);
+        count -= 1;
+    }
+    let mut seed = state.tick ^ 0x5851_F42D_4C95_7F2D;
+    let candidates: Vec<usize> = (0..assets.len()).collect();
+    while count < target {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let Some(idx) = pick_seeded(&candidates, seed) else {
+            break;
+        };
+        let (fw, fh) = (assets[idx].width, assets[idx].height);
+        let x = (seed >> 16) as usize % new_size.0.saturating_sub(fw).max(1);
+        // Above the seabed under the fish, if any.
+        let floor = floor_row(state, x as isize, fw);
+        let y = (seed >> 36) as usize % floor.saturating_sub(fh).max(1);
+        // Classic pacing: 2.5..12.5 cells per second, either direction.
+        let speed = 2.5 + ((seed >> 8) % 100) as f32 / 10.0;
+        let vx = if seed & 1 == 0 { speed } else { -speed };
+        state.fishes.push(FishInstance {
+            fish_art_index: idx,
+            position: (x as f32, y as f32),
+            velocity: (vx, 0.0),
+        });
+        state.fish_behaviors.push(FishBehavior::Normal);
+        state.fish_cruises.push(None);
+        count += 1;
+    }
+    true
+}
+
 /// Render the aquarium state into a single string (newline-separated).
+/// Every row is `state.size.0` cells wide; wide glyphs count as two.
+pub fn render_aquarium_to_string(state: &AquariumState, assets: &[FishArt]) -> String {
+    render_aquarium_to_grid(state, assets).to_text()
+}
+
+/// Compose the aquarium state into a cell grid (see `render_aquarium_to_string`).
 ///
 /// Order:
 /// - Waterlines (background)
-/// - Castle (bottom-right)
+/// - Decorations below `FOREGROUND_DEPTH` (the castle)
 /// - Seaweed (foreground under fish)
 /// - Fish
+/// - Decorations from `FOREGROUND_DEPTH`
 /// - Bubbles (top-most)
-pub fn render_aquarium_to_string(state: &AquariumState, assets: &[FishArt]) -> String {
+pub fn render_aquarium_to_grid<'a>(
+    state: &'a AquariumState,
+    assets: &'a [FishArt],
+) -> CellGrid<'a> {
     let (w, h) = state.size;
+    let mut grid = CellGrid::new(w, h);
     if w == 0 || h == 0 {
-        return String::new();
+        return grid;
     }
 
-    let mut grid = vec![' '; w * h];
-
     // 1) Waterlines with per-column vertical offsets for wave dynamics.
-    let patterns: [Vec<char>; 4] = [
-        WATER_LINES[0].chars().collect(),
-        WATER_LINES[1].chars().collect(),
-        WATER_LINES[2].chars().collect(),
-        WATER_LINES[3].chars().collect(),
+    let patterns: [Vec<&'static str>; 4] = [
+        graphemes(WATER_LINES[0]).collect(),
+        graphemes(WATER_LINES[1]).collect(),
+        graphemes(WATER_LINES[2]).collect(),
+        graphemes(WATER_LINES[3]).collect(),
     ];
     let plens = [
         patterns[0].len().max(1),
@@ -747,18 +1508,9 @@ pub fn render_aquarium_to_string(state: &AquariumState, assets: &[FishArt]) -> S
         patterns[3].len().max(1),
     ];
 
+    let weather = state.env.weather.kind;
     for x in 0..w {
-        // Triangular wave over columns with phase: 0 -> 1 -> 2 -> 1 repeating.
-        let t = (state.env.water_phase as usize + x) % 24;
-        let v_off: usize = if t < 6 {
-            0
-        } else if t < 12 {
-            1
-        } else if t < 18 {
-            2
-        } else {
-            1
-        };
+        let v_off = wave_offset(weather, state.env.water_phase, x);
 
         for i in 0..4 {
             if i >= h {
@@ -768,242 +1520,137 @@ pub fn render_aquarium_to_string(state: &AquariumState, assets: &[FishArt]) -> S
             if y >= h {
                 continue;
             }
-            let off = (state.env.water_phase as usize) % plens[i];
-            let ch = patterns[i][(x + off) % plens[i]];
-            grid[y * w + x] = ch;
+            // Calm water does not scroll.
+            let off = match weather {
+                WeatherKind::Calm => 0,
+                _ => (state.env.water_phase as usize) % plens[i],
+            };
+            let Some(g) = patterns[i].get((x + off) % plens[i]) else {
+                continue;
+            };
+            grid.put(x as isize, y as isize, g);
         }
     }
 
+    // Moon reflection on the waterline at night.
+    grid.set_pen(Layer::Effect);
+    for (x, y, g) in moon_reflection(state) {
+        grid.put(x, y, g);
+    }
+
+    // Ship wakes on the waterline, behind the ships.
+    grid.set_pen(Layer::Water);
+    for (x, y, g) in wake_cells(state) {
+        grid.put(x, y, g);
+    }
+
     // Render ships over waterlines near the surface.
-    for ship in &state.env.ships {
-        let x0 = ship.x.floor() as isize;
-        // Subtle vertical bob (amplitude 1) with slow phase
-        let phase_u = (state.tick / 64) % 2;
-        let phase_i = (x0.rem_euclid(2)) as u64;
-        let bob = if (phase_u + phase_i) % 2 == 0 { 0 } else { 1 };
-        let y0 = ship.y as isize + bob;
-        let art = if ship.vx >= 0.0 { SHIP_R } else { SHIP_L };
-        for (dy, line) in art.lines().enumerate() {
-            let y = y0 + dy as isize;
-            if y < 0 || y >= h as isize {
-                continue;
-            }
-            for (dx, ch) in line.chars().enumerate() {
-                if ch == ' ' || ch == '?' {
-                    continue;
-                }
-                let x = x0 + dx as isize;
-                if x < 0 || x >= w as isize {
-                    continue;
-                }
-                grid[y as usize * w + x as usize] = ch;
-            }
-        }
+    grid.set_pen(Layer::Visitor);
+    for (i, ship) in state.env.ships.iter().enumerate() {
+        blit_sprite(&mut grid, &ship_sprite(state, i, ship));
     }
 
-    // 2) Castle at bottom-right if enabled.
-    if state.env.castle {
-        let (cw, ch) = measure_block(CASTLE);
-        let base_x = w.saturating_sub(cw + 1);
-        let base_y = h.saturating_sub(ch);
-        for (dy, line) in CASTLE.lines().enumerate() {
-            let y = base_y + dy;
-            if y >= h {
-                continue;
-            }
-            for (dx, ch) in line.chars().enumerate() {
-                if ch == ' ' || ch == '?' {
-                    continue;
-                }
-                let x = base_x + dx;
-                if x >= w {
-                    continue;
-                }
-                grid[y * w + x] = ch;
-            }
+    // Raindrops and splashes, in front of ships.
+    grid.set_pen(Layer::Water);
+    for (x, y, g) in rain_cells(state) {
+        grid.put(x, y, g);
+    }
+
+    // Seabed terrain along the bottom.
+    grid.set_pen(Layer::Scenery);
+    if let Some(seabed) = &state.env.seabed {
+        for (x, y, g) in seabed_cells(seabed, (w, h)) {
+            grid.put(x, y, g);
         }
     }
 
+    // 2) Decorations behind seaweed and fish (the castle).
+    for sprite in decoration_sprites(state, false) {
+        blit_sprite(&mut grid, &sprite);
+    }
+
     // 3) Seaweed stalks, swaying slightly with water_phase + per-stalk phase.
     for (idx, stalk) in state.env.seaweed.iter().enumerate() {
-        let base_y = h.saturating_sub(stalk.height);
-        // sway: -1, 0, +1 cycling at a slow rate
-        let phase = (state.env.water_phase.wrapping_add(stalk.sway_phase)) / 8;
-        let sway = match phase % 3 {
-            0 => -1isize,
-            1 => 0isize,
-            _ => 1isize,
-        };
-        // Draw alternating '(' and ')' vertically.
-        for dy in 0..stalk.height {
-            let y = base_y + dy;
-            if y >= h {
-                continue;
-            }
-            let left = dy % 2 == 0;
-            let x_base = stalk.x as isize + if left { 0 } else { 1 };
-            let x = x_base + sway;
-            if x < 0 || (x as usize) >= w {
-                continue;
-            }
-            grid[y * w + (x as usize)] = if left { '(' } else { ')' };
-        }
-        // Slight horizontal spread for some stalks to avoid uniformity.
-        if idx % 3 == 0 {
-            let x2 = (stalk.x + 1).min(w.saturating_sub(1));
-            for dy in 1..stalk.height {
-                let y = base_y + dy;
-                if y >= h {
-                    continue;
-                }
-                let x = x2 as isize + sway;
-                if x < 0 || (x as usize) >= w {
-                    continue;
-                }
-                if dy % 2 == 0 {
-                    grid[y * w + (x as usize)] = '(';
-                } else {
-                    grid[y * w + (x as usize)] = ')';
-                }
-            }
+        for (x, y, g) in seaweed_cells(state, idx, stalk) {
+            grid.put(x, y, g);
         }
     }
 
     // Render whales (with spout) and sharks under water.
-    for whale in &state.env.whales {
-        let x0 = whale.x.floor() as isize;
-        // Subtle vertical bob (amplitude 1) with slow phase
-        let phase_u = (state.tick / 96) % 2;
-        let phase_i = (x0.rem_euclid(2)) as u64;
-        let bob = if (phase_u + phase_i) % 2 == 0 { 0 } else { 1 };
-        let y0 = whale.y as isize + bob;
-        let art = if whale.vx >= 0.0 { WHALE_R } else { WHALE_L };
-        // Whale body
-        for (dy, line) in art.lines().enumerate() {
-            let y = y0 + dy as isize;
-            if y < 0 || y >= h as isize {
-                continue;
-            }
-            for (dx, ch) in line.chars().enumerate() {
-                if ch == ' ' || ch == '?' {
-                    continue;
-                }
-                let x = x0 + dx as isize;
-                if x < 0 || x >= w as isize {
-                    continue;
-                }
-                grid[y as usize * w + x as usize] = ch;
-            }
-        }
-        // Water spout above head (simple animation)
-        let frame = (state.tick as usize / 12) % SPOUT_FRAMES.len();
-        let spout = SPOUT_FRAMES[frame];
-        // Approximate blowhole position a bit right of whale x
-        let spx = x0 + if whale.vx >= 0.0 { 8 } else { 3 };
-        let spy = y0.saturating_sub(3);
-        for (dy, line) in spout.lines().enumerate() {
-            let y = spy + dy as isize;
-            if y < 0 || y >= h as isize {
-                continue;
-            }
-            for (dx, ch) in line.chars().enumerate() {
-                if ch == ' ' || ch == '?' {
-                    continue;
-                }
-                let x = spx + dx as isize;
-                if x < 0 || x >= w as isize {
-                    continue;
-                }
-                grid[y as usize * w + x as usize] = ch;
-            }
+    grid.set_pen(Layer::Visitor);
+    for (i, whale) in state.env.whales.iter().enumerate() {
+        for part in whale_sprites(state, i, whale) {
+            blit_sprite(&mut grid, &part);
         }
     }
 
-    for shark in &state.env.sharks {
-        let x0 = shark.x.floor() as isize;
-        // Subtle vertical bob (amplitude 1) with slow phase
-        let phase_u = (state.tick / 80) % 2;
-        let phase_i = (x0.rem_euclid(2)) as u64;
-        let bob = if (phase_u + phase_i) % 2 == 0 { 0 } else { 1 };
-        let y0 = shark.y as isize + bob;
-        let art = if shark.vx >= 0.0 { SHARK_R } else { SHARK_L };
-        for (dy, line) in art.lines().enumerate() {
-            let y = y0 + dy as isize;
-            if y < 0 || y >= h as isize {
-                continue;
-            }
-            for (dx, ch) in line.chars().enumerate() {
-                if ch == ' ' || ch == '?' {
-                    continue;
-                }
-                let x = x0 + dx as isize;
-                if x < 0 || x >= w as isize {
-                    continue;
-                }
-                grid[y as usize * w + x as usize] = ch;
-            }
-        }
+    for (i, shark) in state.env.sharks.iter().enumerate() {
+        blit_sprite(&mut grid, &shark_sprite(state, i, shark));
     }
 
-    // 4) Fish (overdraw seaweed/castle/water where they overlap).
-    for fish in &state.fishes {
-        let art = match assets.get(fish.fish_art_index) {
-            Some(a) => a,
-            None => continue,
-        };
-        let x0 = fish.position.0.floor() as isize;
-        let y0 = fish.position.1.floor() as isize;
-
-        let mirror = {
-            let prefers_right = art_prefers_right(art.art);
-            (fish.velocity.0 < 0.0 && prefers_right) || (fish.velocity.0 > 0.0 && !prefers_right)
-        };
-        for (dy, raw_line) in art.art.lines().enumerate() {
-            let y = y0 + dy as isize;
-            if y < 0 || y >= h as isize {
-                continue;
-            }
-
-            let line_str = if mirror {
-                mirror_ascii_line(raw_line)
-            } else {
-                raw_line.to_string()
-            };
-
-            for (dx, ch) in line_str.chars().enumerate() {
-                if ch == ' ' || ch == '?' {
-                    continue;
-                }
-                let x = x0 + dx as isize;
-                if x < 0 || x >= w as isize {
-                    continue;
-                }
-                grid[y as usize * w + x as usize] = ch;
-            }
+    // 4) Fish (overdraw seaweed/castle/water where they overlap), over any food.
+    grid.set_pen(Layer::Fish);
+    for (x, y, g) in food_cells(state) {
+        grid.put(x, y, g);
+    }
+    for (i, fish) in state.fishes.iter().enumerate() {
+        if let Some(sprite) = fish_sprite(assets, i, fish) {
+            blit_sprite(&mut grid, &sprite);
         }
     }
 
-    // 5) Bubbles (top-most), simple '.' markers with clipping.
+    // Decorations in front of fish.
+    grid.set_pen(Layer::Scenery);
+    for sprite in decoration_sprites(state, true) {
+        blit_sprite(&mut grid, &sprite);
+    }
+
+    // 5) Bubbles (top-most): growing `.`/`o`/`O`, or a pop on the surface.
+    grid.set_pen(Layer::Bubble);
     for b in &state.bubbles {
         let x = b.position.0.floor() as isize;
         let y = b.position.1.floor() as isize;
-        if x < 0 || x >= w as isize || y < 0 || y >= h as isize {
-            continue;
-        }
-        grid[y as usize * w + x as usize] = '.';
+        grid.put(x, y, b.glyph());
     }
 
-    // Join into a single string with newline separators.
-    let mut out = String::with_capacity((w + 1) * h);
-    for row in 0..h {
-        let start = row * w;
-        let end = start + w;
-        out.extend(grid[start..end].iter().copied());
-        if row + 1 < h {
-            out.push('\n');
-        }
+    grid
+}
+
+/// Colour for a glyph in the colorized renderers: water, seaweed and bubbles use the
+/// palette, everything else (castle, ship, fish bodies) `text_color`.
+pub fn palette_color(
+    glyph: &str,
+    pal: &AsciiquariumPalette,
+    text_color: egui::Color32,
+) -> egui::Color32 {
+    match glyph.chars().next().unwrap_or(' ') {
+        // Water surface
+        '~' | '^' => pal.water,
+        // Seaweed
+        '(' | ')' => pal.seaweed,
+        // Bubbles
+        '.' => pal.bubble,
+        // Default fish and all other glyphs
+        _ => text_color,
+    }
+}
+
+/// Colour for a glyph under `theme`: `palette_color` when colour is enabled and a palette
+/// is set, otherwise `text_color`.
+pub fn glyph_color(glyph: &str, theme: &AsciiquariumTheme) -> egui::Color32 {
+    match (&theme.palette, theme.enable_color) {
+        (Some(pal), true) => palette_color(glyph, pal, theme.text_color),
+        _ => theme.text_color,
+    }
+}
+
+/// Colour for a cell drawn by `layer`: bubble cells use the palette's bubble colour whatever
+/// their glyph (`o` and `O` are fish glyphs too), everything else `glyph_color`.
+pub fn cell_color(glyph: &str, layer: Layer, theme: &AsciiquariumTheme) -> egui::Color32 {
+    match (&theme.palette, theme.enable_color, layer) {
+        (Some(pal), true, Layer::Bubble) => pal.bubble,
+        _ => glyph_color(glyph, theme),
     }
-    out
 }
 
 /// egui widget: stateless, renders from AquariumState + assets + theme.
@@ -1015,35 +1662,39 @@ pub struct AsciiquariumWidget<'a> {
 
 impl<'a> egui::Widget for AsciiquariumWidget<'a> {
     fn ui(self, ui: &mut egui::Ui) -> egui::Response {
-        // Always render the ASCII grid string first
-        let rendered_string = render_aquarium_to_string(self.state, self.assets);
+        // Always compose the cell grid first
+        let grid = render_aquarium_to_grid(self.state, self.assets);
+        let shade = shade_for(self.state);
+        let background = match (self.theme.background, &shade) {
+            (Some(fill), Some(shade)) => Some(shade.background(fill)),
+            (fill, _) => fill,
+        };
+        // Without per-cell colours the whole text takes the tint of the surface row.
+        let text_color = match &shade {
+            Some(shade) => shade.color(self.theme.text_color, Layer::Water, 0),
+            None => self.theme.text_color,
+        };
 
         // If color is enabled and a palette is provided, build a colorized LayoutJob.
         let response = if self.theme.enable_color {
-            if let Some(pal) = &self.theme.palette {
+            if self.theme.palette.is_some() {
                 let mut job = egui::text::LayoutJob::default();
-                // Make sure this uses a monospace font
-                let mono = egui::FontId::monospace(12.0);
-
-                for (row_idx, line) in rendered_string.lines().enumerate() {
-                    for ch in line.chars() {
-                        let color = match ch {
-                            // Water surface
-                            '~' | '^' => pal.water,
-                            // Seaweed
-                            '(' | ')' => pal.seaweed,
-                            // Castle (fallback to text color; many different chars)
-                            // We leave castle to default unless specifically themed elsewhere.
-                            // Ship (same approach as castle)
-                            // Bubbles
-                            '.' => pal.bubble,
-                            // Mask placeholders from original assets: color as subtle water trail
-                            '?' => pal.water_trail,
-                            // Default fish and all other glyphs
-                            _ => self.theme.text_color,
+                // Same monospace font as the plain path, so auto-fit metrics hold for both
+                let mono = egui::TextStyle::Monospace.resolve(ui.style());
+
+                for row_idx in 0..grid.height {
+                    for (x, cell) in grid.row(row_idx).iter().enumerate() {
+                        // Wide glyphs are appended once; their continuation cell has no text.
+                        let Cell::Glyph(g) = *cell else {
+                            continue;
                         };
+                        let layer = grid.layer(x, row_idx).unwrap_or_default();
+                        let mut color = cell_color(g, layer, self.theme);
+                        if let Some(shade) = &shade {
+                            color = shade.color(color, layer, row_idx);
+                        }
                         job.append(
-                            &ch.to_string(),
+                            g,
                             0.0,
                             egui::TextFormat {
                                 font_id: mono.clone(),
@@ -1067,7 +1718,7 @@ impl<'a> egui::Widget for AsciiquariumWidget<'a> {
 
                 let label =
                     egui::Label::new(egui::WidgetText::LayoutJob(job)).wrap(self.theme.wrap);
-                if let Some(fill) = self.theme.background {
+                if let Some(fill) = background {
                     egui::Frame::default()
                         .fill(fill)
                         .show(ui, |ui| ui.add(label))
@@ -1077,11 +1728,11 @@ impl<'a> egui::Widget for AsciiquariumWidget<'a> {
                 }
             } else {
                 // Palette missing, fall back to plain text
-                let text = egui::RichText::new(rendered_string)
+                let text = egui::RichText::new(grid.to_text())
                     .monospace()
-                    .color(self.theme.text_color);
+                    .color(text_color);
                 let label = egui::Label::new(text).wrap(self.theme.wrap);
-                if let Some(fill) = self.theme.background {
+                if let Some(fill) = background {
                     egui::Frame::default()
                         .fill(fill)
                         .show(ui, |ui| ui.add(label))
@@ -1092,11 +1743,11 @@ impl<'a> egui::Widget for AsciiquariumWidget<'a> {
             }
         } else {
             // Plain text path (default)
-            let text = egui::RichText::new(rendered_string)
+            let text = egui::RichText::new(grid.to_text())
                 .monospace()
-                .color(self.theme.text_color);
+                .color(text_color);
             let label = egui::Label::new(text).wrap(self.theme.wrap);
-            if let Some(fill) = self.theme.background {
+            if let Some(fill) = background {
                 egui::Frame::default()
                     .fill(fill)
                     .show(ui, |ui| ui.add(label))
@@ -1115,11 +1766,7 @@ mod tests {
     use super::*;
 
     fn mk_assets() -> Vec<FishArt> {
-        vec![FishArt {
-            art: "<>",
-            width: 2,
-            height: 1,
-        }]
+        vec![FishArt::new("<>")]
     }
 
     #[test]
@@ -1159,4 +1806,231 @@ mod tests {
         // Expect multiple rows; ensure first visible char is still fish '>' due to overdraw.
         assert!(s.lines().next().unwrap_or("").starts_with('>'));
     }
+
+    #[test]
+    fn mirrored_art_keeps_lines_aligned() {
+        let art = FishArt::new("  __\n><_'>");
+        assert_eq!(art.facing, Facing::Right);
+        assert_eq!(art.art_facing(Facing::Left), " __\n<'_><");
+        assert_eq!(art.facing_for_velocity(0.0), Facing::Right);
+        assert_eq!(art.facing_for_velocity(-1.0), Facing::Left);
+    }
+
+    #[test]
+    fn interior_spaces_are_opaque() {
+        let assets = vec![FishArt::new("#######"), FishArt::new("  < ?>")];
+        let mut state = AquariumState {
+            size: (7, 1),
+            ..Default::default()
+        };
+        state.env.decorations.clear();
+        for idx in 0..2 {
+            state.fishes.push(FishInstance {
+                fish_art_index: idx,
+                position: (0.0, 0.0),
+                velocity: (0.0, 0.0),
+            });
+        }
+        let s = render_aquarium_to_string(&state, &assets);
+        // Leading blanks and `?` show the fish behind; the interior space hides it.
+        assert_eq!(s, "##< #>#");
+    }
+
+    #[test]
+    fn wide_glyphs_keep_rows_aligned() {
+        let assets = vec![FishArt::new("🐟>")];
+        assert_eq!(assets[0].width, 3);
+        assert_eq!(assets[0].flipped, "<🐟");
+        let mut state = AquariumState {
+            size: (4, 1),
+            ..Default::default()
+        };
+        state.env.decorations.clear();
+        state.fishes.push(FishInstance {
+            fish_art_index: 0,
+            position: (-1.0, 0.0),
+            velocity: (0.0, 0.0),
+        });
+        // The glyph's left half is off-screen, so its visible half is blanked over the water.
+        assert_eq!(render_aquarium_to_string(&state, &assets), " >~~");
+    }
+
+    #[test]
+    fn shrinking_relocates_and_culls() {
+        let assets = mk_assets();
+        let mut state = AquariumState {
+            size: (80, 24),
+            ..Default::default()
+        };
+        state.fishes.push(FishInstance {
+            fish_art_index: 0,
+            position: (70.0, 20.0),
+            velocity: (1.0, 0.0),
+        });
+        state.env.seaweed.push(Seaweed {
+            x: 60,
+            height: 6,
+            sway_phase: 0,
+        });
+        state.bubbles.push(Bubble::new((50.0, 10.0), (0.0, -3.0)));
+        assert!(set_aquarium_size(&mut state, &assets, (40, 12)));
+        assert_eq!(state.fishes[0].position, (38.0, 11.0));
+        assert!(state.env.seaweed.is_empty());
+        assert!(state.bubbles.is_empty());
+        assert!(!set_aquarium_size(&mut state, &assets, (40, 12)));
+    }
+
+    #[test]
+    fn resize_rescales_and_keeps_density() {
+        let assets = mk_assets();
+        let mut state = AquariumState {
+            size: (80, 24),
+            ..Default::default()
+        };
+        for i in 0..4 {
+            state.fishes.push(FishInstance {
+                fish_art_index: 0,
+                position: (10.0 * i as f32, 12.0),
+                velocity: (1.0, 0.0),
+            });
+        }
+        state.fish_behaviors = vec![FishBehavior::Normal; 4];
+        update_aquarium(&mut state, &assets);
+        let stalks = state.env.seaweed.clone();
+        let shark_y = state.env.sharks.first().map(|s| s.y);
+
+        assert!(resize_aquarium(&mut state, &assets, (160, 48)));
+        assert_eq!(state.fishes.len(), 16);
+        assert!((state.fishes[1].position.1 - 24.0).abs() < 0.5);
+        assert_eq!(state.env.seaweed.len(), 10);
+        for old in &stalks {
+            assert!(state.env.seaweed.iter().any(|s| s.x == old.x * 2
+                && s.height == old.height
+                && s.sway_phase == old.sway_phase));
+        }
+        assert_ne!(state.env.sharks.first().map(|s| s.y), shark_y);
+
+        resize_aquarium(&mut state, &assets, (80, 24));
+        assert_eq!(state.fishes.len(), 4);
+        assert_eq!(state.env.seaweed.len(), 5);
+
+        // Fish moved, clamped or re-seeded by a resize stay above the sand.
+        state.env.seabed = Some(Seabed::new(3).with_max_height(8));
+        for size in [(160, 48), (80, 12), (200, 10)] {
+            resize_aquarium(&mut state, &assets, size);
+            assert!(state.fishes.iter().all(|f| {
+                let floor = floor_row(&state, f.position.0.floor() as isize, 2);
+                f.position.1 + 1.0 <= floor as f32
+            }));
+        }
+    }
+
+    #[test]
+    fn resize_keeps_host_fish_and_survives_zero_size() {
+        let assets = mk_assets();
+        let mut state = AquariumState {
+            size: (80, 24),
+            ..Default::default()
+        };
+        for i in 0..4 {
+            state.fishes.push(FishInstance {
+                fish_art_index: 0,
+                position: (10.0 * i as f32, 12.0),
+                velocity: (1.0, 0.0),
+            });
+        }
+        state.fish_behaviors = vec![FishBehavior::Normal; 4];
+        resize_aquarium(&mut state, &assets, (160, 48));
+        assert_eq!(state.fishes.len(), 16);
+        // A fish added between resizes counts toward the density.
+        state.fishes.push(FishInstance {
+            fish_art_index: 0,
+            position: (5.0, 5.0),
+            velocity: (-2.0, 0.0),
+        });
+        state.fish_behaviors.push(FishBehavior::Normal);
+        resize_aquarium(&mut state, &assets, (320, 24));
+        assert_eq!(state.fishes.len(), 17);
+        assert!(state.fishes.iter().any(|f| f.velocity == (-2.0, 0.0)));
+        resize_aquarium(&mut state, &assets, (160, 48));
+        assert_eq!(state.fishes.len(), 17);
+
+        // Minimised and restored: the same fish come back, none culled or re-seeded.
+        let fish = state.fishes.clone();
+        resize_aquarium(&mut state, &assets, (0, 48));
+        assert_eq!(state.fishes.len(), 17);
+        resize_aquarium(&mut state, &assets, (160, 48));
+        assert_eq!(state.fishes.len(), 17);
+        assert!(state
+            .fishes
+            .iter()
+            .zip(&fish)
+            .all(|(a, b)| a.fish_art_index == b.fish_art_index && a.velocity == b.velocity));
+    }
+
+    #[test]
+    fn emitters_follow_stalks_dropped_by_a_resize() {
+        use crate::widgets::bubbles::EmitterAnchor;
+        let mut state = AquariumState {
+            size: (80, 24),
+            ..Default::default()
+        };
+        state.env.auto_seaweed = false;
+        for x in [70, 10, 40] {
+            state.env.seaweed.push(Seaweed {
+                x,
+                height: 4,
+                sway_phase: 0,
+            });
+        }
+        for i in [0, 1, 2] {
+            state
+                .env
+                .emitters
+                .push(BubbleEmitter::new(EmitterAnchor::Seaweed(i)));
+        }
+        set_aquarium_size(&mut state, &[], (50, 24));
+        assert_eq!(state.env.seaweed.len(), 2);
+        let anchors: Vec<_> = state.env.emitters.iter().map(|e| e.anchor).collect();
+        assert_eq!(
+            anchors,
+            [EmitterAnchor::Seaweed(0), EmitterAnchor::Seaweed(1)]
+        );
+        assert_eq!(state.env.seaweed[1].x, 40);
+
+        // Minimised: no stalks, so no emitters pointing at them.
+        resize_aquarium(&mut state, &[], (0, 0));
+        assert!(state.env.seaweed.is_empty() && state.env.emitters.is_empty());
+    }
+
+    #[test]
+    fn scared_fish_dart_away_then_calm_down() {
+        let assets = mk_assets();
+        let mut state = AquariumState {
+            size: (80, 24),
+            ..Default::default()
+        };
+        // The third fish already cruises at darting speed.
+        for (x, vx) in [(20.0, -5.0), (60.0, -5.0), (40.0, SCARE_SPEED)] {
+            state.fishes.push(FishInstance {
+                fish_art_index: 0,
+                position: (x, 12.0),
+                velocity: (vx, 0.0),
+            });
+        }
+        assert_eq!(scare_fish(&mut state, &assets, (18.0, 12.0)), 1);
+        assert_eq!(scare_fish(&mut state, &assets, (38.0, 12.0)), 1);
+        assert_eq!(state.fishes[0].velocity, (SCARE_SPEED, CRUISE_MAX_VY * 4.0));
+        assert_eq!(state.fish_cruises[0], Some(Cruise::Darting((-5.0, 0.0))));
+        assert_eq!(state.fishes[1].velocity.0, -5.0);
+        for _ in 0..150 {
+            update_aquarium(&mut state, &assets);
+        }
+        // Back to the old speed (whichever way it now faces) and level again.
+        for (fish, speed) in state.fishes.iter().zip([5.0, 5.0, SCARE_SPEED]) {
+            assert_eq!(fish.velocity.0.abs(), speed);
+            assert_eq!(fish.velocity.1, 0.0);
+        }
+        assert_eq!(state.fish_cruises, [None; 3]);
+    }
 }
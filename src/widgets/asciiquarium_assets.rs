@@ -2,7 +2,7 @@
 Asciiquarium assets: a small curated set of ASCII fish plus a helper to auto-measure width and height for any multi-line ASCII art.
 
 Notes:
-- Width is the maximum visible character count across all lines (Unicode scalar count).
+- Width is the maximum display width across all lines, in cells (wide glyphs count as two).
 - Height is the total number of lines returned by `.lines()`.
 - Leading/trailing blank lines in raw strings will count toward height.
 - Rendering code will clip as needed; assets need not avoid whitespace.
@@ -15,10 +15,11 @@ Example:
 ```
 */
 
-use super::asciiquarium::FishArt;
+use super::asciiquarium::{Facing, FishArt, Transparency};
+use super::text_width::display_width;
 
-const FISH_01: &str = r#"<º)))><"#; // Facing right
-const FISH_02: &str = r#"><(((º>"#; // Facing left
+const FISH_01: &str = r#"<º)))><"#; // Facing left
+const FISH_02: &str = r#"><(((º>"#; // Facing right
 
 // A larger, multi-line fish (faces right on the first line)
 const FISH_03: &str = r#"
@@ -109,14 +110,44 @@ const FISH_13: &str = r#"
  \`
 "#;
 
+/// A sprite extracted from the original script (see `generated_assets`): animation frames
+/// and colour masks. `masks` is empty when the original has none, and a single mask applies
+/// to every frame. `transparency` is the rule the original entity is drawn with.
+#[derive(Debug, Clone, Copy)]
+pub struct GeneratedSprite {
+    pub frames: &'static [&'static str],
+    pub masks: &'static [&'static str],
+    pub transparency: Transparency,
+}
+
+impl GeneratedSprite {
+    /// Frame `i`, wrapping around the animation. Empty if there are no frames.
+    pub fn frame(&self, i: usize) -> &'static str {
+        if self.frames.is_empty() {
+            ""
+        } else {
+            self.frames[i % self.frames.len()]
+        }
+    }
+
+    /// Mask for frame `i`, if the sprite has masks.
+    pub fn mask(&self, i: usize) -> Option<&'static str> {
+        if self.masks.is_empty() {
+            None
+        } else {
+            Some(self.masks[i % self.masks.len()])
+        }
+    }
+}
+
 /// Measure an ASCII art block's dimensions as (width, height),
-/// where width is the maximum character count of any line and height
+/// where width is the maximum display width (in cells) of any line and height
 /// is the total number of lines. Guarantees minimum size of 1x1.
 pub fn measure_art(art: &str) -> (usize, usize) {
     let mut max_w = 0usize;
     let mut h = 0usize;
     for line in art.lines() {
-        let w = line.chars().count();
+        let w = display_width(line);
         if w > max_w {
             max_w = w;
         }
@@ -127,18 +158,31 @@ pub fn measure_art(art: &str) -> (usize, usize) {
 
 /// Returns a vector of `FishArt` with auto-measured width/height.
 /// Add more constants above and insert them in the list below to expand the set.
+///
+/// Right/left pairs are declared explicitly and use each other as their hand-drawn
+/// opposite-facing art; unpaired fish fall back to the facing heuristic and mirroring.
 pub fn get_fish_assets() -> Vec<FishArt> {
+    const PAIRS: [(&str, &str); 5] = [
+        (FISH_02, FISH_01),
+        (FISH_06, FISH_07),
+        (FISH_08, FISH_09),
+        (FISH_10, FISH_11),
+        (FISH_12, FISH_13),
+    ];
     let mut out = Vec::new();
     for art in [
         FISH_01, FISH_02, FISH_03, FISH_04, FISH_05, FISH_06, FISH_07, FISH_08, FISH_09, FISH_10,
         FISH_11, FISH_12, FISH_13,
     ] {
-        let (w, h) = measure_art(art);
-        out.push(FishArt {
-            art,
-            width: w,
-            height: h,
-        });
+        let mut fish = FishArt::new(art);
+        for (right, left) in PAIRS {
+            if art == right {
+                fish = fish.with_facing(Facing::Right).with_flipped(left);
+            } else if art == left {
+                fish = fish.with_facing(Facing::Left).with_flipped(right);
+            }
+        }
+        out.push(fish);
     }
     out
 }
@@ -172,4 +216,13 @@ mod tests {
             assert!(!a.art.is_empty());
         }
     }
+
+    #[test]
+    fn pairs_use_hand_drawn_flips() {
+        let assets = get_fish_assets();
+        assert_eq!(assets[0].facing, Facing::Left);
+        assert_eq!(assets[0].art_facing(Facing::Right), FISH_02);
+        assert_eq!(assets[1].facing, Facing::Right);
+        assert_eq!(assets[1].art_facing(Facing::Left), FISH_01);
+    }
 }
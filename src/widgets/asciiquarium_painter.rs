@@ -0,0 +1,171 @@
+/*!
+Painter-based Asciiquarium widget: paints the cell grid directly with `ui.painter()`.
+
+Agent Log:
+- Added as a faster alternative to `AsciiquariumWidget`, which builds a `LayoutJob` with one
+  section per character every frame.
+- Each row is split into runs of one colour; each run is laid out as a single galley and
+  painted at its cell position. Blank cells never produce galleys.
+- egui's galley cache keeps runs that did not change since the last frame (most of the
+  water, castle and seaweed), so only moving sprites are laid out again.
+- Wide glyphs are painted on their own at their cell, so a font whose emoji are not exactly
+  two cells wide cannot shift the rest of the row.
+- Same colouring as the label widget (`shade_color`, including the day/night shade);
+  `theme.wrap` does not apply.
+- `benches/render.rs` compares both widgets on a 200x60 tank.
+
+Example:
+```text
+ui.add(AsciiquariumPainter { state: &state, assets: registry.assets(), theme: &theme });
+```
+*/
+
+use egui;
+
+use super::asciiquarium::{render_aquarium_to_grid, AquariumState, AsciiquariumTheme, FishArt};
+use super::auto_fit::cell_size;
+use super::cell_grid::{Cell, CellGrid};
+use super::day_night::{shade_color, shade_for, Shade};
+use super::text_width::grapheme_width;
+
+/// egui widget: stateless, paints from AquariumState + assets + theme.
+pub struct AsciiquariumPainter<'a> {
+    pub state: &'a AquariumState,
+    pub assets: &'a [FishArt],
+    pub theme: &'a AsciiquariumTheme,
+}
+
+/// A run of same-coloured cells on one row.
+pub(crate) struct Span {
+    pub(crate) col: usize,
+    pub(crate) text: String,
+    pub(crate) color: egui::Color32,
+}
+
+/// Split row `y` of `grid` into coloured runs. Blanks join the current run (so a fish
+/// body stays one galley) but never start one; wide glyphs always get their own run.
+/// Also used by the HTML and SVG exporters.
+pub(crate) fn row_spans(
+    grid: &CellGrid<'_>,
+    y: usize,
+    theme: &AsciiquariumTheme,
+    shade: Option<&Shade>,
+) -> Vec<Span> {
+    let mut spans: Vec<Span> = Vec::new();
+    let mut open = false;
+    for (x, cell) in grid.row(y).iter().enumerate() {
+        let Cell::Glyph(g) = *cell else {
+            continue;
+        };
+        if g == " " {
+            if open {
+                if let Some(span) = spans.last_mut() {
+                    span.text.push(' ');
+                }
+            }
+            continue;
+        }
+        let color = shade_color(grid, x, y, g, theme, shade);
+        let wide = grapheme_width(g) > 1;
+        let extend = open && !wide && spans.last().is_some_and(|s| s.color == color);
+        if extend {
+            if let Some(span) = spans.last_mut() {
+                span.text.push_str(g);
+            }
+        } else {
+            spans.push(Span {
+                col: x,
+                text: g.to_string(),
+                color,
+            });
+        }
+        open = !wide;
+    }
+    for span in &mut spans {
+        span.text.truncate(span.text.trim_end().len());
+    }
+    spans
+}
+
+/// Paint `grid` into `rect` (background first), one galley per coloured run, shaded by
+/// `shade` if the day/night cycle is on.
+pub fn paint_grid(
+    painter: &egui::Painter,
+    rect: egui::Rect,
+    cell: egui::Vec2,
+    font_id: &egui::FontId,
+    grid: &CellGrid<'_>,
+    theme: &AsciiquariumTheme,
+    shade: Option<&Shade>,
+) {
+    if let Some(fill) = theme.background {
+        let fill = shade.map_or(fill, |s| s.background(fill));
+        painter.rect_filled(rect, 0.0, fill);
+    }
+    for y in 0..grid.height {
+        for span in row_spans(grid, y, theme, shade) {
+            let pos = rect.min + egui::vec2(span.col as f32 * cell.x, y as f32 * cell.y);
+            let galley = painter.layout_no_wrap(span.text, font_id.clone(), span.color);
+            painter.galley(pos, galley, span.color);
+        }
+    }
+}
+
+impl<'a> egui::Widget for AsciiquariumPainter<'a> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let grid = render_aquarium_to_grid(self.state, self.assets);
+        let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+        let cell = cell_size(ui, &font_id);
+        let size = egui::vec2(grid.width as f32 * cell.x, grid.height as f32 * cell.y);
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+        if !ui.is_rect_visible(rect) {
+            return response;
+        }
+
+        paint_grid(
+            &ui.painter_at(rect),
+            rect,
+            cell,
+            &font_id,
+            &grid,
+            self.theme,
+            shade_for(self.state).as_ref(),
+        );
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::asciiquarium::AsciiquariumPalette;
+
+    #[test]
+    fn spans_merge_by_colour_and_skip_blanks() {
+        let mut grid = CellGrid::new(10, 1);
+        for (x, g) in ["~", "~", " ", "<", " ", ">", " ", " ", "~"]
+            .iter()
+            .enumerate()
+        {
+            grid.put(x as isize, 0, g);
+        }
+        let theme = AsciiquariumTheme {
+            enable_color: true,
+            palette: Some(AsciiquariumPalette {
+                water: egui::Color32::BLUE,
+                water_trail: egui::Color32::BLUE,
+                seaweed: egui::Color32::GREEN,
+                castle: egui::Color32::GRAY,
+                ship: egui::Color32::GRAY,
+                bubble: egui::Color32::WHITE,
+                shark: egui::Color32::GRAY,
+                whale: egui::Color32::GRAY,
+                fish: egui::Color32::YELLOW,
+            }),
+            ..Default::default()
+        };
+        let spans = row_spans(&grid, 0, &theme, None);
+        let texts: Vec<(usize, &str)> = spans.iter().map(|s| (s.col, s.text.as_str())).collect();
+        assert_eq!(texts, vec![(0, "~~"), (3, "< >"), (8, "~")]);
+    }
+}
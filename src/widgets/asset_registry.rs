@@ -0,0 +1,251 @@
+/*!
+Asset registry: stable string IDs and tags for fish art.
+
+Agent Log:
+- Added `AssetRegistry` so hosts and the spawner address fish by ID instead of raw indices
+  into a concatenated `Vec<FishArt>`.
+- Registration only appends, so an index handed out by the registry never shifts.
+- Entries are tagged automatically (size class, facing, source); callers can add more tags.
+- Queries are deterministic (seeded picks), in line with the rest of the core crate.
+
+Example:
+```text
+let registry = AssetRegistry::with_defaults();
+let idx = registry.pick(&[AssetTag::Small], seed);
+update_aquarium(&mut state, registry.assets());
+```
+*/
+
+use super::asciiquarium::{Facing, FishArt, FishInstance};
+use super::asciiquarium_assets::get_fish_assets;
+use super::generated_assets::get_generated_fish_assets;
+
+/// Fish at most this many rows tall count as small. Mirrors the original, where sharks
+/// only eat fish with `height <= 5`.
+pub const SMALL_FISH_MAX_HEIGHT: usize = 5;
+
+/// Tags used to query the registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetTag {
+    /// At most `SMALL_FISH_MAX_HEIGHT` rows tall.
+    Small,
+    /// Taller than `SMALL_FISH_MAX_HEIGHT` rows.
+    Large,
+    /// Art is drawn facing left.
+    LeftFacing,
+    /// Extracted from the original Perl script.
+    Classic,
+    /// Part of the hand-made starter set.
+    Manual,
+}
+
+/// Whether an art block counts as a small fish.
+pub fn is_small_fish(art: &FishArt) -> bool {
+    art.height <= SMALL_FISH_MAX_HEIGHT
+}
+
+/// Tags derived from the art itself (size class and facing).
+pub fn auto_tags(art: &FishArt) -> Vec<AssetTag> {
+    let mut tags = Vec::with_capacity(2);
+    tags.push(if is_small_fish(art) {
+        AssetTag::Small
+    } else {
+        AssetTag::Large
+    });
+    if art.facing == Facing::Left {
+        tags.push(AssetTag::LeftFacing);
+    }
+    tags
+}
+
+/// Deterministically pick one candidate from a seed. Returns None when empty.
+pub fn pick_seeded(candidates: &[usize], seed: u64) -> Option<usize> {
+    if candidates.is_empty() {
+        return None;
+    }
+    let mixed = (seed ^ 0x9E37_79B9_7F4A_7C15).wrapping_mul(6364136223846793005);
+    candidates
+        .get((mixed >> 33) as usize % candidates.len())
+        .copied()
+}
+
+/// Registry metadata for one asset.
+#[derive(Debug, Clone)]
+pub struct AssetEntry {
+    /// Stable identifier, e.g. `"manual/fish_03"` or `"classic/fish_0012"`.
+    pub id: String,
+    pub tags: Vec<AssetTag>,
+}
+
+/// Fish assets addressed by stable IDs and tags.
+///
+/// `assets()` is the slice passed to `update_aquarium`, `render_aquarium_to_string` and the
+/// widget; `FishInstance::fish_art_index` values obtained from the registry index into it.
+#[derive(Debug, Clone, Default)]
+pub struct AssetRegistry {
+    arts: Vec<FishArt>,
+    entries: Vec<AssetEntry>,
+}
+
+impl AssetRegistry {
+    /// An empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The manual starter set (`manual/fish_NN`) followed by the classic fish extracted
+    /// from the original script (`classic/fish_NNNN`).
+    pub fn with_defaults() -> Self {
+        let mut reg = Self::new();
+        for (i, art) in get_fish_assets().into_iter().enumerate() {
+            reg.register(
+                format!("manual/fish_{:02}", i + 1),
+                art,
+                &[AssetTag::Manual],
+            );
+        }
+        for (i, art) in get_generated_fish_assets().into_iter().enumerate() {
+            reg.register(
+                format!("classic/fish_{:04}", i + 1),
+                art,
+                &[AssetTag::Classic],
+            );
+        }
+        reg
+    }
+
+    /// Register an asset under `id` with automatic tags plus `extra_tags`.
+    /// Returns the asset index, or None if `id` is already taken.
+    pub fn register(
+        &mut self,
+        id: impl Into<String>,
+        art: FishArt,
+        extra_tags: &[AssetTag],
+    ) -> Option<usize> {
+        let id = id.into();
+        if self.index_of(&id).is_some() {
+            return None;
+        }
+        let mut tags = auto_tags(&art);
+        for tag in extra_tags {
+            if !tags.contains(tag) {
+                tags.push(*tag);
+            }
+        }
+        self.arts.push(art);
+        self.entries.push(AssetEntry { id, tags });
+        Some(self.arts.len() - 1)
+    }
+
+    /// All assets in registration order.
+    pub fn assets(&self) -> &[FishArt] {
+        &self.arts
+    }
+
+    pub fn len(&self) -> usize {
+        self.arts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.arts.is_empty()
+    }
+
+    /// Registry metadata in registration order (parallel to `assets()`).
+    pub fn entries(&self) -> &[AssetEntry] {
+        &self.entries
+    }
+
+    /// Resolve a stable ID to its asset index.
+    pub fn index_of(&self, id: &str) -> Option<usize> {
+        self.entries.iter().position(|e| e.id == id)
+    }
+
+    /// Look up an asset by ID.
+    pub fn get(&self, id: &str) -> Option<&FishArt> {
+        self.index_of(id).and_then(|i| self.arts.get(i))
+    }
+
+    /// The ID of the asset at `index`.
+    pub fn id_of(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(|e| e.id.as_str())
+    }
+
+    /// Tags of the asset at `index` (empty if out of range).
+    pub fn tags_of(&self, index: usize) -> &[AssetTag] {
+        self.entries
+            .get(index)
+            .map(|e| e.tags.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Indices of all assets carrying every tag in `tags` (all assets when `tags` is empty).
+    pub fn query(&self, tags: &[AssetTag]) -> Vec<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| tags.iter().all(|t| e.tags.contains(t)))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Deterministically pick one asset carrying every tag in `tags`,
+    /// e.g. "random small fish" is `pick(&[AssetTag::Small], seed)`.
+    pub fn pick(&self, tags: &[AssetTag], seed: u64) -> Option<usize> {
+        pick_seeded(&self.query(tags), seed)
+    }
+
+    /// Build a fish instance for the asset `id`. Returns None for unknown IDs.
+    pub fn spawn(
+        &self,
+        id: &str,
+        position: (f32, f32),
+        velocity: (f32, f32),
+    ) -> Option<FishInstance> {
+        self.index_of(id).map(|fish_art_index| FishInstance {
+            fish_art_index,
+            position,
+            velocity,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn art(s: &'static str) -> FishArt {
+        FishArt::new(s)
+    }
+
+    #[test]
+    fn ids_survive_concatenation_order() {
+        let reg = AssetRegistry::with_defaults();
+        let manual = get_fish_assets().len();
+        assert_eq!(reg.index_of("manual/fish_01"), Some(0));
+        assert_eq!(reg.index_of("classic/fish_0001"), Some(manual));
+        assert_eq!(reg.id_of(manual), Some("classic/fish_0001"));
+        assert!(reg.tags_of(manual).contains(&AssetTag::Classic));
+    }
+
+    #[test]
+    fn duplicate_ids_are_rejected() {
+        let mut reg = AssetRegistry::new();
+        assert_eq!(reg.register("a", art("<>"), &[]), Some(0));
+        assert_eq!(reg.register("a", art("><"), &[]), None);
+        assert_eq!(reg.len(), 1);
+    }
+
+    #[test]
+    fn pick_respects_tags() {
+        let mut reg = AssetRegistry::new();
+        reg.register("big", art("\n\n\n\n\n\n<>"), &[]);
+        reg.register("small", art("<'))><"), &[]);
+        reg.register("other", art("><(('>"), &[]);
+        for seed in 0..32 {
+            let i = reg.pick(&[AssetTag::Small], seed);
+            assert!(i == Some(1) || i == Some(2));
+        }
+        assert_eq!(reg.query(&[AssetTag::Large]), vec![0]);
+        assert_eq!(reg.pick(&[AssetTag::Large, AssetTag::Manual], 7), None);
+    }
+}
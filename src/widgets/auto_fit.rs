@@ -0,0 +1,69 @@
+/*!
+Auto-fit: size the aquarium grid to the space egui gives the widget.
+
+Agent Log:
+- Added so hosts no longer pick `AquariumState::size` by hand.
+- Columns and rows come from `ui.available_size()` and the monospace glyph metrics the
+  widgets draw with (`TextStyle::Monospace`).
+- Widgets only borrow the state, so fitting is a helper the host calls right before adding
+  one; `resize_aquarium` rescales the scene to the new bounds.
+
+Example:
+```text
+egui::CentralPanel::default().show(ctx, |ui| {
+    fit_aquarium_to_ui(ui, &mut state, registry.assets());
+    ui.add(AsciiquariumPainter { state: &state, assets: registry.assets(), theme: &theme });
+});
+```
+*/
+
+use egui;
+
+use super::asciiquarium::{resize_aquarium, AquariumState, FishArt};
+
+/// Size of one grid cell in points for the monospace font `font_id`.
+pub fn cell_size(ui: &egui::Ui, font_id: &egui::FontId) -> egui::Vec2 {
+    ui.fonts(|f| egui::vec2(f.glyph_width(font_id, 'M'), f.row_height(font_id)))
+}
+
+/// Whole cells of `cell` that fit in `available` as (columns, rows).
+pub fn grid_size_for_cells(available: egui::Vec2, cell: egui::Vec2) -> (usize, usize) {
+    let fit = |space: f32, step: f32| {
+        if step > 0.0 && space.is_finite() && space > 0.0 {
+            (space / step).floor() as usize
+        } else {
+            0
+        }
+    };
+    (fit(available.x, cell.x), fit(available.y, cell.y))
+}
+
+/// Columns and rows of the widgets' monospace font that fit in `available`.
+pub fn grid_size_for(ui: &egui::Ui, available: egui::Vec2) -> (usize, usize) {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    grid_size_for_cells(available, cell_size(ui, &font_id))
+}
+
+/// Resize `state` to fill `ui.available_size()`. Returns true if the size changed.
+pub fn fit_aquarium_to_ui(ui: &egui::Ui, state: &mut AquariumState, assets: &[FishArt]) -> bool {
+    let size = grid_size_for(ui, ui.available_size());
+    resize_aquarium(state, assets, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whole_cells_only() {
+        let cell = egui::vec2(7.0, 14.0);
+        assert_eq!(
+            grid_size_for_cells(egui::vec2(700.0, 300.0), cell),
+            (100, 21)
+        );
+        assert_eq!(
+            grid_size_for_cells(egui::vec2(6.9, f32::INFINITY), cell),
+            (0, 0)
+        );
+    }
+}
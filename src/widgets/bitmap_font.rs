@@ -0,0 +1,208 @@
+/*!
+Bitmap font: an embedded 5x7 monospace font for printable ASCII.
+
+Agent Log:
+- Added for the software rasteriser (`png_export`), which runs without a GPU or window and so
+  cannot use egui's fonts.
+- Glyphs are hand-drawn 5x7 bitmaps in the style of classic LCD fonts, one `u8` per row with
+  the left column in bit 4; lowercase descenders are squeezed into the seven rows.
+- A glyph sits in a 6x12 design cell (one column of spacing, rows above and below), about the
+  shape of a terminal cell. `ink` scales the design cell to any cell size, nearest neighbour.
+- The few non-ASCII glyphs the stock art uses (the `º` fish eyes) are in `EXTRA`; anything
+  else outside printable ASCII draws as a hollow box (`MISSING`).
+*/
+
+/// Glyph size in font pixels.
+pub const GLYPH_W: usize = 5;
+pub const GLYPH_H: usize = 7;
+/// Design cell size in font pixels.
+pub const CELL_W: usize = 6;
+pub const CELL_H: usize = 12;
+/// Design cell rows above the glyph.
+const TOP: usize = 2;
+
+/// Drawn for characters outside printable ASCII.
+const MISSING: [u8; GLYPH_H] = [
+    0b11111, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11111,
+];
+
+/// Printable ASCII from `' '` to `'~'`.
+#[rustfmt::skip]
+const FONT: [[u8; GLYPH_H]; 95] = [
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000], // space
+    [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100], // !
+    [0b01010, 0b01010, 0b01010, 0b00000, 0b00000, 0b00000, 0b00000], // "
+    [0b01010, 0b01010, 0b11111, 0b01010, 0b11111, 0b01010, 0b01010], // #
+    [0b00100, 0b01111, 0b10100, 0b01110, 0b00101, 0b11110, 0b00100], // $
+    [0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011], // %
+    [0b01100, 0b10010, 0b10100, 0b01000, 0b10101, 0b10010, 0b01101], // &
+    [0b00100, 0b00100, 0b01000, 0b00000, 0b00000, 0b00000, 0b00000], // '
+    [0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00010], // (
+    [0b01000, 0b00100, 0b00010, 0b00010, 0b00010, 0b00100, 0b01000], // )
+    [0b00000, 0b00100, 0b10101, 0b01110, 0b10101, 0b00100, 0b00000], // *
+    [0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000], // +
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b00100, 0b01000], // ,
+    [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000], // -
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100], // .
+    [0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000], // /
+    [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110], // 0
+    [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110], // 1
+    [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111], // 2
+    [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110], // 3
+    [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010], // 4
+    [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110], // 5
+    [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110], // 6
+    [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000], // 7
+    [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110], // 8
+    [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100], // 9
+    [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000], // :
+    [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b00100, 0b01000], // ;
+    [0b00010, 0b00100, 0b01000, 0b10000, 0b01000, 0b00100, 0b00010], // <
+    [0b00000, 0b00000, 0b11111, 0b00000, 0b11111, 0b00000, 0b00000], // =
+    [0b01000, 0b00100, 0b00010, 0b00001, 0b00010, 0b00100, 0b01000], // >
+    [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100], // ?
+    [0b01110, 0b10001, 0b00001, 0b01101, 0b10101, 0b10101, 0b01110], // @
+    [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001], // A
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110], // B
+    [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110], // C
+    [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100], // D
+    [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111], // E
+    [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000], // F
+    [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111], // G
+    [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001], // H
+    [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110], // I
+    [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100], // J
+    [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001], // K
+    [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111], // L
+    [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001], // M
+    [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001], // N
+    [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110], // O
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000], // P
+    [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101], // Q
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001], // R
+    [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110], // S
+    [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100], // T
+    [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110], // U
+    [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100], // V
+    [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010], // W
+    [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001], // X
+    [0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100], // Y
+    [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111], // Z
+    [0b01110, 0b01000, 0b01000, 0b01000, 0b01000, 0b01000, 0b01110], // [
+    [0b00000, 0b10000, 0b01000, 0b00100, 0b00010, 0b00001, 0b00000], // \
+    [0b01110, 0b00010, 0b00010, 0b00010, 0b00010, 0b00010, 0b01110], // ]
+    [0b00100, 0b01010, 0b10001, 0b00000, 0b00000, 0b00000, 0b00000], // ^
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b11111], // _
+    [0b01000, 0b00100, 0b00010, 0b00000, 0b00000, 0b00000, 0b00000], // `
+    [0b00000, 0b00000, 0b01110, 0b00001, 0b01111, 0b10001, 0b01111], // a
+    [0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b11110], // b
+    [0b00000, 0b00000, 0b01110, 0b10000, 0b10000, 0b10001, 0b01110], // c
+    [0b00001, 0b00001, 0b01101, 0b10011, 0b10001, 0b10001, 0b01111], // d
+    [0b00000, 0b00000, 0b01110, 0b10001, 0b11111, 0b10000, 0b01110], // e
+    [0b00110, 0b01001, 0b01000, 0b11100, 0b01000, 0b01000, 0b01000], // f
+    [0b00000, 0b01111, 0b10001, 0b10001, 0b01111, 0b00001, 0b01110], // g
+    [0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001], // h
+    [0b00100, 0b00000, 0b01100, 0b00100, 0b00100, 0b00100, 0b01110], // i
+    [0b00010, 0b00000, 0b00110, 0b00010, 0b00010, 0b10010, 0b01100], // j
+    [0b10000, 0b10000, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010], // k
+    [0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110], // l
+    [0b00000, 0b00000, 0b11010, 0b10101, 0b10101, 0b10001, 0b10001], // m
+    [0b00000, 0b00000, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001], // n
+    [0b00000, 0b00000, 0b01110, 0b10001, 0b10001, 0b10001, 0b01110], // o
+    [0b00000, 0b00000, 0b11110, 0b10001, 0b11110, 0b10000, 0b10000], // p
+    [0b00000, 0b00000, 0b01101, 0b10011, 0b01111, 0b00001, 0b00001], // q
+    [0b00000, 0b00000, 0b10110, 0b11001, 0b10000, 0b10000, 0b10000], // r
+    [0b00000, 0b00000, 0b01110, 0b10000, 0b01110, 0b00001, 0b11110], // s
+    [0b01000, 0b01000, 0b11100, 0b01000, 0b01000, 0b01001, 0b00110], // t
+    [0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b10011, 0b01101], // u
+    [0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100], // v
+    [0b00000, 0b00000, 0b10001, 0b10001, 0b10101, 0b10101, 0b01010], // w
+    [0b00000, 0b00000, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001], // x
+    [0b00000, 0b00000, 0b10001, 0b10001, 0b01111, 0b00001, 0b01110], // y
+    [0b00000, 0b00000, 0b11111, 0b00010, 0b00100, 0b01000, 0b11111], // z
+    [0b00010, 0b00100, 0b00100, 0b01000, 0b00100, 0b00100, 0b00010], // {
+    [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100], // |
+    [0b01000, 0b00100, 0b00100, 0b00010, 0b00100, 0b00100, 0b01000], // }
+    [0b00000, 0b00000, 0b01000, 0b10101, 0b00010, 0b00000, 0b00000], // ~
+];
+
+/// Non-ASCII characters found in the stock art.
+#[rustfmt::skip]
+const EXTRA: [(char, [u8; GLYPH_H]); 1] = [
+    ('º', [0b01110, 0b10001, 0b10001, 0b01110, 0b00000, 0b00000, 0b00000]), // raised o (eyes)
+];
+
+/// Bitmap rows of `ch`.
+pub fn glyph(ch: char) -> [u8; GLYPH_H] {
+    (ch as usize)
+        .checked_sub(' ' as usize)
+        .and_then(|i| FONT.get(i))
+        .or_else(|| EXTRA.iter().find(|(c, _)| *c == ch).map(|(_, rows)| rows))
+        .copied()
+        .unwrap_or(MISSING)
+}
+
+/// Whether pixel (`px`, `py`) of a `cell_w` x `cell_h` pixel cell is inked by `rows`.
+pub fn ink(rows: &[u8; GLYPH_H], px: usize, py: usize, cell_w: usize, cell_h: usize) -> bool {
+    let x = px * CELL_W / cell_w.max(1);
+    let Some(y) = (py * CELL_H / cell_h.max(1)).checked_sub(TOP) else {
+        return false;
+    };
+    x < GLYPH_W && y < GLYPH_H && rows[y] & (1 << (GLYPH_W - 1 - x)) != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::asciiquarium::{
+        render_aquarium_to_string, update_aquarium, AquariumState, Facing, FishInstance,
+    };
+    use crate::widgets::asset_registry::AssetRegistry;
+
+    #[test]
+    fn glyphs_scale_into_cells() {
+        assert_eq!(glyph('|'), [0b00100; GLYPH_H]);
+        assert_eq!(glyph('é'), MISSING);
+        let bar = glyph('|');
+        // At design size the bar is column 2, rows TOP..TOP + 7.
+        assert!(ink(&bar, 2, TOP, CELL_W, CELL_H));
+        assert!(!ink(&bar, 2, TOP - 1, CELL_W, CELL_H));
+        assert!(!ink(&bar, 3, TOP, CELL_W, CELL_H));
+        // Doubled, it is two pixels wide.
+        assert!(ink(&bar, 4, 2 * TOP, 2 * CELL_W, 2 * CELL_H));
+        assert!(ink(&bar, 5, 2 * TOP, 2 * CELL_W, 2 * CELL_H));
+        assert!(!ink(&bar, 6, 2 * TOP, 2 * CELL_W, 2 * CELL_H));
+    }
+
+    #[test]
+    fn stock_tank_has_no_missing_glyphs() {
+        let registry = AssetRegistry::with_defaults();
+        let assets = registry.assets();
+        let mut state = AquariumState {
+            size: (160, 40),
+            ..Default::default()
+        };
+        for (i, art) in assets.iter().enumerate() {
+            for vx in [1.0, -1.0] {
+                state.fishes.push(FishInstance {
+                    fish_art_index: i,
+                    position: ((i * 7 % 140) as f32, (3 + i % 30) as f32),
+                    velocity: (vx, 0.0),
+                });
+            }
+            for facing in [Facing::Left, Facing::Right] {
+                let mut chars = art.art_facing(facing).lines().flat_map(str::chars);
+                assert!(chars.all(|c| glyph(c) != MISSING));
+            }
+        }
+        // Sample frames as ships, sharks and whales come and go.
+        for tick in 0..200 {
+            update_aquarium(&mut state, assets);
+            if tick % 10 != 0 {
+                continue;
+            }
+            let text = render_aquarium_to_string(&state, assets);
+            assert!(text.lines().flat_map(str::chars).all(|c| glyph(c) != MISSING));
+        }
+    }
+}
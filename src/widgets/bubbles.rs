@@ -0,0 +1,294 @@
+/*!
+Bubbles: emitters, growth while rising, popping at the surface and optional merging.
+
+Agent Log:
+- Added to match the original, where bubbles animate `.` -> `o` -> `O` as they rise and die
+  when they touch the waterline. Ours used to be a single `.` culled above the top row.
+- A bubble grows one stage every `GROW_TICKS` ticks (`Bubble::glyph`). It pops when it
+  reaches the lowest water line at its column (`weather::surface_row`), as in the original
+  where it dies touching the bottom waterline entity, so it follows the wave (and the weather). A popped bubble shows `POP_GLYPH` on the surface for
+  `POP_TICKS` ticks, then goes.
+- With `AquariumEnvironment::merge_bubbles`, bubbles that reach the same cell become one,
+  a stage bigger than the bigger of the two. Off by default, like the original.
+- Renderers colour bubble cells by their `Layer`, since `o` and `O` are also fish glyphs.
+- `AquariumEnvironment::emitters` adds sources besides fish mouths: a `BubbleEmitter` is
+  anchored to a decoration (its top centre; e.g. an aerator stone), a seaweed stalk's base or
+  a fixed cell, and releases a burst of `burst` bubbles every `period` ticks. Anchors are
+  indices like `EntityRef`; `remove_entity` drops emitters on removed entities and shifts the
+  rest (`detach_emitters`), and so do resizes that drop seaweed stalks.
+*/
+
+use super::asciiquarium::{floor_row, AquariumState, Bubble, EntityRef};
+use super::current::current_at;
+use super::weather::surface_row;
+
+/// Ticks between growth stages.
+pub const GROW_TICKS: u32 = 12;
+/// Ticks a popped bubble stays on the surface.
+pub const POP_TICKS: u8 = 3;
+const STAGES: [&str; 3] = [".", "o", "O"];
+const POP_GLYPH: &str = "*";
+/// Rise speed of emitted bubbles, as for fish bubbles (cells per second).
+const RISE_SPEED: f32 = 3.0;
+
+/// What a `BubbleEmitter` is attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmitterAnchor {
+    /// Top centre of `env.decorations[i]`.
+    Decoration(usize),
+    /// Base of `env.seaweed[i]`.
+    Seaweed(usize),
+    /// A fixed cell.
+    Cell((usize, usize)),
+}
+
+/// A source of bubbles: a burst of `burst` bubbles, `burst_gap` ticks apart, every `period`
+/// ticks, starting `offset` ticks into the period. Bubbles rise about a row every 10 ticks, so
+/// gaps of 10 or more keep a burst from merging into one row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BubbleEmitter {
+    pub anchor: EmitterAnchor,
+    pub period: u64,
+    pub burst: usize,
+    pub burst_gap: u64,
+    pub offset: u64,
+}
+
+impl BubbleEmitter {
+    /// One bubble every 90 ticks (about 3 seconds).
+    pub fn new(anchor: EmitterAnchor) -> Self {
+        Self {
+            anchor,
+            period: 90,
+            burst: 1,
+            burst_gap: 10,
+            offset: 0,
+        }
+    }
+
+    pub fn with_period(mut self, period: u64) -> Self {
+        self.period = period;
+        self
+    }
+
+    /// `count` bubbles per period, `gap` ticks apart.
+    pub fn with_burst(mut self, count: usize, gap: u64) -> Self {
+        self.burst = count;
+        self.burst_gap = gap;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: u64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Which bubble of the burst leaves at `tick`, if any.
+    pub fn emits(&self, tick: u64) -> Option<usize> {
+        let p = (tick + self.period.max(1) - self.offset % self.period.max(1)) % self.period.max(1);
+        let gap = self.burst_gap.max(1);
+        let i = (p / gap) as usize;
+        (p % gap == 0 && i < self.burst).then_some(i)
+    }
+}
+
+/// Where bubbles from `anchor` start, if what it is attached to exists.
+pub fn emitter_origin(state: &AquariumState, anchor: EmitterAnchor) -> Option<(f32, f32)> {
+    match anchor {
+        EmitterAnchor::Decoration(i) => {
+            let decoration = state.env.decorations.get(i)?;
+            let (x, y) = decoration.origin(state.size, state.env.seabed.as_ref());
+            let (dw, _) = decoration.kind.size();
+            Some((x as f32 + (dw / 2) as f32, y as f32))
+        }
+        EmitterAnchor::Seaweed(i) => {
+            let stalk = state.env.seaweed.get(i)?;
+            let floor = floor_row(state, stalk.x as isize, 2);
+            Some((stalk.x as f32 + 1.0, floor.saturating_sub(1) as f32))
+        }
+        EmitterAnchor::Cell((x, y)) => Some((x as f32, y as f32)),
+    }
+}
+
+/// Release this tick's bubbles from `env.emitters`.
+pub fn emit_bubbles(state: &mut AquariumState) {
+    let mut released = Vec::new();
+    for emitter in &state.env.emitters {
+        if emitter.emits(state.tick).is_none() {
+            continue;
+        }
+        if let Some(position) = emitter_origin(state, emitter.anchor) {
+            released.push(Bubble::new(position, (0.0, -RISE_SPEED)));
+        }
+    }
+    state.bubbles.extend(released);
+}
+
+/// Drop the emitters anchored to `removed` and shift later indices of the same kind down,
+/// matching `Vec::remove` on the decorations or seaweed.
+pub fn detach_emitters(emitters: &mut Vec<BubbleEmitter>, removed: EntityRef) {
+    emitters.retain_mut(|emitter| {
+        let index = match (&mut emitter.anchor, removed) {
+            (EmitterAnchor::Decoration(i), EntityRef::Decoration(r))
+            | (EmitterAnchor::Seaweed(i), EntityRef::Seaweed(r)) => (i, r),
+            _ => return true,
+        };
+        if *index.0 == index.1 {
+            return false;
+        }
+        if *index.0 > index.1 {
+            *index.0 -= 1;
+        }
+        true
+    });
+}
+
+impl Bubble {
+    /// A new, smallest bubble.
+    pub fn new(position: (f32, f32), velocity: (f32, f32)) -> Self {
+        Self {
+            position,
+            velocity,
+            age: 0,
+            popped: 0,
+        }
+    }
+
+    /// Growth stage: 0 (`.`), 1 (`o`) or 2 (`O`).
+    pub fn stage(&self) -> usize {
+        ((self.age / GROW_TICKS) as usize).min(STAGES.len() - 1)
+    }
+
+    /// Glyph drawn for the bubble this tick.
+    pub fn glyph(&self) -> &'static str {
+        if self.popped > 0 {
+            POP_GLYPH
+        } else {
+            STAGES[self.stage()]
+        }
+    }
+
+    fn cell(&self) -> (isize, isize) {
+        (
+            self.position.0.floor() as isize,
+            self.position.1.floor() as isize,
+        )
+    }
+}
+
+/// Rise (drifting with the current), grow, pop at the surface and merge.
+pub fn update_bubbles(state: &mut AquariumState, dt: f32) {
+    let kind = state.env.weather.kind;
+    let phase = state.env.water_phase;
+    let width = state.size.0 as isize;
+    let bubbles = std::mem::take(&mut state.bubbles);
+    let mut kept: Vec<Bubble> = Vec::with_capacity(bubbles.len());
+    for mut b in bubbles {
+        if b.popped > 0 {
+            b.popped -= 1;
+            if b.popped > 0 {
+                kept.push(b);
+            }
+            continue;
+        }
+        let (cx, cy) = current_at(state, b.position);
+        b.position.0 += (b.velocity.0 + cx) * dt;
+        b.position.1 += (b.velocity.1 + cy) * dt;
+        b.age = b.age.saturating_add(1);
+        let (x, y) = b.cell();
+        if x < 0 || x >= width {
+            continue;
+        }
+        let surface = surface_row(kind, phase, x as usize) as isize;
+        if y <= surface {
+            b.position.1 = surface as f32;
+            b.popped = POP_TICKS;
+        }
+        if state.env.merge_bubbles {
+            let other = kept
+                .iter_mut()
+                .find(|o| o.popped == 0 && b.popped == 0 && o.cell() == b.cell());
+            if let Some(other) = other {
+                other.age = other.age.max(b.age) + GROW_TICKS;
+                continue;
+            }
+        }
+        kept.push(b);
+    }
+    state.bubbles = kept;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::asciiquarium::render_aquarium_to_string;
+    use crate::widgets::weather::WeatherKind;
+
+    #[test]
+    fn bubbles_grow_pop_and_merge() {
+        let mut state = AquariumState {
+            size: (40, 20),
+            ..Default::default()
+        };
+        state.env.weather.kind = WeatherKind::Calm;
+        state.bubbles.push(Bubble::new((5.0, 12.0), (0.0, -15.0)));
+        let mut glyphs = Vec::new();
+        for _ in 0..30 {
+            update_bubbles(&mut state, 0.033);
+            glyphs.extend(state.bubbles.first().map(|b| b.glyph()));
+        }
+        assert_eq!(glyphs[0], ".");
+        assert_eq!(glyphs[GROW_TICKS as usize], "o");
+        // Popped on the (flat) surface, then gone.
+        assert!(glyphs.ends_with(&["*"; POP_TICKS as usize]));
+        assert!(state.bubbles.is_empty());
+
+        state.env.merge_bubbles = true;
+        state.bubbles.push(Bubble::new((5.2, 10.0), (0.0, -3.0)));
+        state.bubbles.push(Bubble::new((5.6, 10.0), (0.0, -3.0)));
+        update_bubbles(&mut state, 0.033);
+        assert_eq!(state.bubbles.len(), 1);
+        assert_eq!(state.bubbles[0].glyph(), "o");
+    }
+
+    #[test]
+    fn bubbles_pop_on_the_lowest_water_line() {
+        let mut state = AquariumState {
+            size: (40, 20),
+            ..Default::default()
+        };
+        state.env.weather.kind = WeatherKind::Fair;
+        // Columns where the fair wave dips 0, 1 and 2 rows at phase 0.
+        for (x, row) in [(2, 3), (8, 4), (14, 5)] {
+            state.bubbles = vec![Bubble::new((x as f32, 12.0), (0.0, -15.0))];
+            while state.bubbles[0].popped == 0 {
+                update_bubbles(&mut state, 0.033);
+            }
+            assert_eq!(state.bubbles[0].cell(), (x as isize, row as isize));
+            let text = render_aquarium_to_string(&state, &[]);
+            assert_eq!(text.lines().nth(row).and_then(|l| l.chars().nth(x)), Some('*'));
+        }
+    }
+
+    #[test]
+    fn emitters_burst_and_follow_removals() {
+        let emitter = BubbleEmitter::new(EmitterAnchor::Seaweed(1))
+            .with_period(50)
+            .with_burst(3, 4)
+            .with_offset(10);
+        let ticks: Vec<u64> = (0..100).filter(|&t| emitter.emits(t).is_some()).collect();
+        assert_eq!(ticks, vec![10, 14, 18, 60, 64, 68]);
+
+        let mut emitters = vec![
+            emitter,
+            BubbleEmitter::new(EmitterAnchor::Seaweed(0)),
+            BubbleEmitter::new(EmitterAnchor::Decoration(0)),
+        ];
+        detach_emitters(&mut emitters, EntityRef::Seaweed(0));
+        let anchors: Vec<EmitterAnchor> = emitters.iter().map(|e| e.anchor).collect();
+        assert_eq!(
+            anchors,
+            vec![EmitterAnchor::Seaweed(0), EmitterAnchor::Decoration(0)]
+        );
+    }
+}
@@ -0,0 +1,189 @@
+/*!
+Cell grid the renderer composes a frame into.
+
+Agent Log:
+- Replaced the `Vec<char>` grid so each cell holds a whole grapheme cluster.
+- Wide glyphs occupy two cells: the glyph itself and a `Continuation` cell after it.
+- Overwriting either half of a wide glyph blanks the orphaned half, so rows never drift.
+- A wide glyph that would straddle either edge is clipped (its visible half is blank).
+- `to_text` skips continuation cells, so every row prints exactly `width` cells wide.
+- Each cell also records the `Layer` that drew it (set with `set_pen`), so colour renderers
+  can treat fish, effects and scenery differently without re-running hit-tests.
+*/
+
+use super::text_width::grapheme_width;
+
+/// One cell of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell<'a> {
+    /// A grapheme cluster starting in this cell (one or two cells wide).
+    Glyph(&'a str),
+    /// Right half of the wide glyph in the cell before.
+    Continuation,
+}
+
+impl Cell<'_> {
+    pub const BLANK: Cell<'static> = Cell::Glyph(" ");
+}
+
+/// What drew a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layer {
+    /// Water lines and empty cells.
+    #[default]
+    Water,
+    /// Seabed, decorations and seaweed.
+    Scenery,
+    /// Ships, sharks and whales.
+    Visitor,
+    Fish,
+    Bubble,
+    /// Light effects such as the moon's reflection; never dimmed.
+    Effect,
+}
+
+/// A `width` x `height` grid of cells, row-major.
+#[derive(Debug, Clone)]
+pub struct CellGrid<'a> {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<Cell<'a>>,
+    layers: Vec<Layer>,
+    pen: Layer,
+}
+
+impl<'a> CellGrid<'a> {
+    /// A grid filled with blanks.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::BLANK; width * height],
+            layers: vec![Layer::Water; width * height],
+            pen: Layer::Water,
+        }
+    }
+
+    /// The cell at (x, y), if inside the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<Cell<'a>> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x).copied()
+        } else {
+            None
+        }
+    }
+
+    /// The layer that drew (x, y), if inside the grid.
+    pub fn layer(&self, x: usize, y: usize) -> Option<Layer> {
+        if x < self.width && y < self.height {
+            self.layers.get(y * self.width + x).copied()
+        } else {
+            None
+        }
+    }
+
+    /// Layer recorded for cells drawn by later `put` calls.
+    pub fn set_pen(&mut self, layer: Layer) {
+        self.pen = layer;
+    }
+
+    /// Row `y` as a slice of cells (empty if out of range).
+    pub fn row(&self, y: usize) -> &[Cell<'a>] {
+        if y < self.height {
+            &self.cells[y * self.width..(y + 1) * self.width]
+        } else {
+            &[]
+        }
+    }
+
+    /// Place one grapheme cluster at (x, y), clipping at the edges.
+    /// Returns the number of cells it advances (1 or 2), even when clipped.
+    pub fn put(&mut self, x: isize, y: isize, grapheme: &'a str) -> usize {
+        let cw = grapheme_width(grapheme).max(1);
+        if cw == 2 && x == -1 && y >= 0 && y < self.height as isize && self.width > 0 {
+            // Only the right half of a wide glyph is on-screen: leave it blank.
+            self.clear(0, y as usize);
+            return cw;
+        }
+        if y < 0 || y >= self.height as isize || x < 0 || x >= self.width as isize {
+            return cw;
+        }
+        let (x, y) = (x as usize, y as usize);
+        if x + cw > self.width {
+            // Only part of a wide glyph fits: leave its visible half blank.
+            self.clear(x, y);
+            return cw;
+        }
+        self.clear(x, y);
+        if cw == 2 {
+            self.clear(x + 1, y);
+        }
+        self.cells[y * self.width + x] = Cell::Glyph(grapheme);
+        self.layers[y * self.width + x] = self.pen;
+        if cw == 2 {
+            self.cells[y * self.width + x + 1] = Cell::Continuation;
+            self.layers[y * self.width + x + 1] = self.pen;
+        }
+        cw
+    }
+
+    /// Blank the cell at (x, y), and the other half if it belongs to a wide glyph.
+    fn clear(&mut self, x: usize, y: usize) {
+        let i = y * self.width + x;
+        match self.cells[i] {
+            Cell::Continuation => {
+                if x > 0 {
+                    self.cells[i - 1] = Cell::BLANK;
+                }
+            }
+            Cell::Glyph(g) if grapheme_width(g) == 2 && x + 1 < self.width => {
+                self.cells[i + 1] = Cell::BLANK;
+            }
+            Cell::Glyph(_) => {}
+        }
+        self.cells[i] = Cell::BLANK;
+    }
+
+    /// Rows joined with `\n`, each exactly `width` cells wide.
+    pub fn to_text(&self) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for y in 0..self.height {
+            for cell in self.row(y) {
+                if let Cell::Glyph(g) = cell {
+                    out.push_str(g);
+                }
+            }
+            if y + 1 < self.height {
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::text_width::graphemes;
+
+    #[test]
+    fn wide_glyphs_take_two_cells() {
+        let mut grid = CellGrid::new(5, 1);
+        let mut x = 0;
+        for g in graphemes("🐟>") {
+            x += grid.put(x, 0, g) as isize;
+        }
+        assert_eq!(grid.get(1, 0), Some(Cell::Continuation));
+        assert_eq!(grid.to_text(), "🐟>  ");
+    }
+
+    #[test]
+    fn overwriting_half_clears_the_other() {
+        let mut grid = CellGrid::new(4, 1);
+        grid.put(0, 0, "魚");
+        grid.put(1, 0, "x");
+        assert_eq!(grid.to_text(), " x  ");
+        grid.put(3, 0, "魚");
+        assert_eq!(grid.to_text(), " x  ");
+    }
+}
@@ -0,0 +1,107 @@
+/*!
+Host-fed simulation clock: runs fixed `CLASSIC_DT` ticks for the time the host says passed.
+
+Agent Log:
+- Added for the web build. The core never reads a clock (`std::time::Instant` and
+  `SystemTime::now` panic on `wasm32-unknown-unknown`); every tick is a fixed step, and hosts
+  feed elapsed seconds from whatever they have (egui's `InputState::unstable_dt`, a
+  `requestAnimationFrame` timestamp, a server loop).
+- `SimClock::advance` keeps the remainder between calls, so the tank runs at the same speed
+  whatever the repaint rate. After a long gap (a hidden browser tab, a laptop waking up) at
+  most `max_ticks` run and the rest is dropped instead of fast-forwarding.
+- `speed` scales the time fed in; 0 holds the tank still without losing the remainder.
+*/
+
+use super::asciiquarium::{update_aquarium, AquariumState, FishArt, CLASSIC_DT};
+
+/// Ticks run by one `advance` at most, by default (about a quarter of a second).
+pub const DEFAULT_MAX_TICKS: u32 = 8;
+
+/// Turns host-supplied elapsed time into simulation ticks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimClock {
+    /// Seconds fed in but not yet simulated (less than one tick after `advance`).
+    pub pending: f32,
+    /// Multiplier on the elapsed time; 1.0 is real time.
+    pub speed: f32,
+    /// Most ticks run per `advance`.
+    pub max_ticks: u32,
+}
+
+impl Default for SimClock {
+    fn default() -> Self {
+        Self {
+            pending: 0.0,
+            speed: 1.0,
+            max_ticks: DEFAULT_MAX_TICKS,
+        }
+    }
+}
+
+impl SimClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    pub fn with_max_ticks(mut self, max_ticks: u32) -> Self {
+        self.max_ticks = max_ticks;
+        self
+    }
+
+    /// Add `elapsed` seconds and run the ticks now due. Returns the number of ticks run.
+    /// Negative or non-finite `elapsed` counts as no time.
+    pub fn advance(&mut self, state: &mut AquariumState, assets: &[FishArt], elapsed: f32) -> u32 {
+        let elapsed = if elapsed.is_finite() {
+            (elapsed * self.speed).max(0.0)
+        } else {
+            0.0
+        };
+        self.pending += elapsed;
+        let due = (self.pending / CLASSIC_DT).floor();
+        let ticks = (due as u32).min(self.max_ticks);
+        if due > self.max_ticks as f32 {
+            // Too far behind: drop the backlog, keep the phase.
+            self.pending %= CLASSIC_DT;
+        } else {
+            self.pending -= ticks as f32 * CLASSIC_DT;
+        }
+        for _ in 0..ticks {
+            update_aquarium(state, assets);
+        }
+        ticks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ticks_follow_fed_time_not_calls() {
+        let mut state = AquariumState {
+            size: (40, 12),
+            ..Default::default()
+        };
+        let mut clock = SimClock::new();
+        // One simulated second in 60 small steps and in 4 big ones gives the same tick count.
+        let small: u32 = (0..60)
+            .map(|_| clock.advance(&mut state, &[], 1.0 / 60.0))
+            .sum();
+        let mut other = SimClock::new();
+        let big: u32 = (0..4).map(|_| other.advance(&mut state, &[], 0.25)).sum();
+        assert_eq!((small, big), (30, 30));
+        assert_eq!(state.tick, 60);
+        // A long gap is capped, and bad input is ignored.
+        assert_eq!(clock.advance(&mut state, &[], 10.0), DEFAULT_MAX_TICKS);
+        assert!(clock.pending < CLASSIC_DT);
+        assert_eq!(clock.advance(&mut state, &[], f32::NAN), 0);
+        assert_eq!(clock.advance(&mut state, &[], -1.0), 0);
+        let mut paused = SimClock::new().with_speed(0.0);
+        assert_eq!(paused.advance(&mut state, &[], 1.0), 0);
+    }
+}
@@ -0,0 +1,131 @@
+/*!
+Water current: a 2D flow field that drifts bubbles and weak swimmers and bends seaweed.
+
+Agent Log:
+- Added so bubbles do not always rise straight up; `AquariumEnvironment::current` is None by
+  default (still water).
+- Three shapes: `Uniform` everywhere, `Layered` (blended from the surface to the bottom, e.g.
+  a surface drift over still deep water) and `Eddies` (swirls that fade out at their radius).
+  Velocities are in cells per second, like fish and bubble velocities.
+- The current moves positions, not velocities, so fish keep facing where they swim. Bubbles
+  drift fully, small fish (`is_small_fish`) by `SMALL_FISH_DRIFT`, bigger fish and visitors
+  not at all.
+- Seaweed leans with the horizontal current at its stalk, tips more than roots
+  (`seaweed_lean`), through `seaweed_cells`, so hit-testing follows the bend.
+- `current_at` is the lookup for anything else that should drift (e.g. food).
+*/
+
+use super::asciiquarium::AquariumState;
+
+/// Fraction of the current that moves small fish.
+pub const SMALL_FISH_DRIFT: f32 = 0.5;
+/// Current speed (cells per second) that bends a stalk's tip by one column.
+const BEND_SPEED: f32 = 2.0;
+/// Furthest a tip leans, in columns.
+const MAX_LEAN: f32 = 2.0;
+
+/// A swirl in an `Eddies` current.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Eddy {
+    /// Centre cell.
+    pub center: (f32, f32),
+    /// Cells from the centre where the swirl fades to nothing.
+    pub radius: f32,
+    /// Speed halfway out, in cells per second; positive turns clockwise on screen.
+    pub strength: f32,
+}
+
+/// A flow field over the tank.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Current {
+    /// The same velocity everywhere.
+    Uniform((f32, f32)),
+    /// `surface` at the top row, blending linearly to `bottom` at the bottom row.
+    Layered {
+        surface: (f32, f32),
+        bottom: (f32, f32),
+    },
+    /// Sum of swirls; still water outside them.
+    Eddies(Vec<Eddy>),
+}
+
+impl Current {
+    /// Velocity (cells per second) at `pos` in a tank of `size`.
+    pub fn velocity_at(&self, pos: (f32, f32), size: (usize, usize)) -> (f32, f32) {
+        match self {
+            Current::Uniform(v) => *v,
+            Current::Layered { surface, bottom } => {
+                let depth = (pos.1 / size.1.saturating_sub(1).max(1) as f32).clamp(0.0, 1.0);
+                (
+                    surface.0 + (bottom.0 - surface.0) * depth,
+                    surface.1 + (bottom.1 - surface.1) * depth,
+                )
+            }
+            Current::Eddies(eddies) => eddies.iter().fold((0.0, 0.0), |acc, eddy| {
+                let (dx, dy) = (pos.0 - eddy.center.0, pos.1 - eddy.center.1);
+                let r = (dx * dx + dy * dy).sqrt();
+                if r <= f32::EPSILON || r >= eddy.radius {
+                    return acc;
+                }
+                // Solid-body near the centre, fading to zero at the radius.
+                let speed = eddy.strength * 4.0 * (r / eddy.radius) * (1.0 - r / eddy.radius);
+                (acc.0 - dy / r * speed, acc.1 + dx / r * speed)
+            }),
+        }
+    }
+}
+
+/// Current velocity at `pos`, or still water without a current.
+pub fn current_at(state: &AquariumState, pos: (f32, f32)) -> (f32, f32) {
+    state
+        .env
+        .current
+        .as_ref()
+        .map_or((0.0, 0.0), |c| c.velocity_at(pos, state.size))
+}
+
+/// Columns the seaweed cell `dy` rows below the top of a `height`-row stalk at column `x`
+/// leans with the current; the tip leans most, the root not at all.
+pub fn seaweed_lean(
+    state: &AquariumState,
+    x: usize,
+    top: usize,
+    height: usize,
+    dy: usize,
+) -> isize {
+    if state.env.current.is_none() || height == 0 {
+        return 0;
+    }
+    let mid = (x as f32, top as f32 + height as f32 / 2.0);
+    let tip = (current_at(state, mid).0 / BEND_SPEED).clamp(-MAX_LEAN, MAX_LEAN);
+    let reach = (height - dy.min(height)) as f32 / height as f32;
+    (tip * reach).round() as isize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_blend_and_swirl() {
+        let layered = Current::Layered {
+            surface: (4.0, 0.0),
+            bottom: (0.0, 0.0),
+        };
+        assert_eq!(layered.velocity_at((3.0, 0.0), (40, 11)), (4.0, 0.0));
+        assert_eq!(layered.velocity_at((3.0, 5.0), (40, 11)), (2.0, 0.0));
+        assert_eq!(layered.velocity_at((3.0, 10.0), (40, 11)), (0.0, 0.0));
+
+        let eddies = Current::Eddies(vec![Eddy {
+            center: (10.0, 10.0),
+            radius: 6.0,
+            strength: 2.0,
+        }]);
+        // Right of the centre the flow goes down (clockwise), above it to the right.
+        let right = eddies.velocity_at((13.0, 10.0), (40, 20));
+        assert!(right.0.abs() < 1e-5 && (right.1 - 2.0).abs() < 1e-5);
+        let above = eddies.velocity_at((10.0, 7.0), (40, 20));
+        assert!(above.0 > 0.0);
+        assert_eq!(eddies.velocity_at((30.0, 10.0), (40, 20)), (0.0, 0.0));
+    }
+}
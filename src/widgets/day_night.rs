@@ -0,0 +1,282 @@
+/*!
+Day/night cycle: time of day, palette tinting, depth dimming and night effects.
+
+Agent Log:
+- Added as an optional layer over the static palette; `AquariumEnvironment::day_night` is None
+  by default and nothing changes.
+- Time comes from ticks (`DayClock::Ticks`) or from the host (`DayClock::Hour`, e.g. from the
+  wall clock via `hour_from_unix_seconds`); the core never reads the system clock.
+- Colours are multiplied by a tint interpolated between night, dawn, day and dusk keyframes,
+  then dimmed with depth. The same `Shade` is used by the label widget and the painter, and
+  `shade_color` gives text exporters the colour for any cell.
+- At night fish glow (their colour is pulled towards `GLOW`) and the moon's reflection moves
+  across the waterline. The reflection is drawn into the grid as glyphs, so plain-text
+  renderers show it too.
+*/
+
+use egui::Color32;
+
+use super::asciiquarium::{cell_color, AquariumState, AsciiquariumTheme};
+use super::cell_grid::{CellGrid, Layer};
+
+/// Colour fish are pulled towards at night.
+pub const GLOW: Color32 = Color32::from_rgb(90, 255, 200);
+const NIGHT_TINT: [f32; 3] = [0.35, 0.45, 0.8];
+/// (hour, RGB tint, night strength); the last entry wraps to the first.
+const KEYFRAMES: [(f32, [f32; 3], f32); 7] = [
+    (0.0, NIGHT_TINT, 1.0),
+    (5.0, NIGHT_TINT, 1.0),
+    (6.5, [1.0, 0.75, 0.65], 0.2),
+    (9.0, [1.0, 1.0, 1.0], 0.0),
+    (16.5, [1.0, 1.0, 1.0], 0.0),
+    (18.5, [0.9, 0.55, 0.45], 0.3),
+    (20.0, NIGHT_TINT, 1.0),
+];
+/// Hours from moonrise to moonset, for the reflection's path across the tank.
+const MOONRISE: f32 = 20.0;
+const MOON_HOURS: f32 = 9.0;
+/// Moon reflection, one pattern per waterline row (spaces leave the water untouched).
+const MOON_ROWS: [&str; 3] = ["-*-", "=-=", " - "];
+
+/// Where the time of day comes from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DayClock {
+    /// A full day every `ticks_per_day` ticks, starting at `start_hour`.
+    Ticks { ticks_per_day: u64, start_hour: f32 },
+    /// Set by the host (0.0..24.0), e.g. from the wall clock.
+    Hour(f32),
+}
+
+/// Day/night settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayNight {
+    pub clock: DayClock,
+    /// How much darker the bottom row is than the top (0.0..1.0).
+    pub depth_dimming: f32,
+    /// Fish glow at night.
+    pub glowing_fish: bool,
+    /// The moon is reflected on the waterline at night.
+    pub moon: bool,
+}
+
+impl Default for DayNight {
+    /// A ten-minute day at 30 ticks per second, starting mid-morning.
+    fn default() -> Self {
+        Self {
+            clock: DayClock::Ticks {
+                ticks_per_day: 18_000,
+                start_hour: 9.0,
+            },
+            depth_dimming: 0.5,
+            glowing_fish: true,
+            moon: true,
+        }
+    }
+}
+
+impl DayNight {
+    /// Hour of day (0.0..24.0) at `tick`.
+    pub fn hour(&self, tick: u64) -> f32 {
+        match self.clock {
+            DayClock::Ticks {
+                ticks_per_day,
+                start_hour,
+            } => {
+                let day = ticks_per_day.max(1);
+                let t = (tick % day) as f32 / day as f32;
+                (start_hour + t * 24.0).rem_euclid(24.0)
+            }
+            DayClock::Hour(hour) => hour.rem_euclid(24.0),
+        }
+    }
+}
+
+/// Local hour of day for a Unix timestamp, e.g. from `SystemTime::now()` in the host (or
+/// `Date.now()` on the web, where `SystemTime::now()` panics).
+pub fn hour_from_unix_seconds(secs: u64, utc_offset_minutes: i32) -> f32 {
+    let local = secs as i64 + utc_offset_minutes as i64 * 60;
+    local.rem_euclid(86_400) as f32 / 3600.0
+}
+
+/// Tint and night strength (0.0 day .. 1.0 night) at `hour`.
+pub fn tint_at(hour: f32) -> ([f32; 3], f32) {
+    let hour = hour.rem_euclid(24.0);
+    for (i, &(h0, tint0, night0)) in KEYFRAMES.iter().enumerate() {
+        let (h1, tint1, night1) =
+            KEYFRAMES
+                .get(i + 1)
+                .copied()
+                .unwrap_or((24.0, KEYFRAMES[0].1, KEYFRAMES[0].2));
+        if hour < h1 {
+            let t = (hour - h0) / (h1 - h0);
+            let lerp = |a: f32, b: f32| a + (b - a) * t;
+            let tint = [
+                lerp(tint0[0], tint1[0]),
+                lerp(tint0[1], tint1[1]),
+                lerp(tint0[2], tint1[2]),
+            ];
+            return (tint, lerp(night0, night1));
+        }
+    }
+    (NIGHT_TINT, 1.0)
+}
+
+/// Colour adjustments for one frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shade {
+    pub tint: [f32; 3],
+    /// 0.0 at day, 1.0 at night.
+    pub night: f32,
+    pub depth_dimming: f32,
+    pub glowing_fish: bool,
+    /// Tank height, for depth dimming.
+    pub rows: usize,
+}
+
+/// This frame's shade, if the day/night cycle is on.
+pub fn shade_for(state: &AquariumState) -> Option<Shade> {
+    let cycle = state.env.day_night.as_ref()?;
+    let (tint, night) = tint_at(cycle.hour(state.tick));
+    Some(Shade {
+        tint,
+        night,
+        depth_dimming: cycle.depth_dimming.clamp(0.0, 1.0),
+        glowing_fish: cycle.glowing_fish,
+        rows: state.size.1,
+    })
+}
+
+fn scale(color: Color32, f: [f32; 3]) -> Color32 {
+    let [r, g, b, a] = color.to_array();
+    let ch = |c: u8, f: f32| (c as f32 * f).round().clamp(0.0, a as f32) as u8;
+    Color32::from_rgba_premultiplied(ch(r, f[0]), ch(g, f[1]), ch(b, f[2]), a)
+}
+
+fn mix(a: Color32, b: Color32, t: f32) -> Color32 {
+    let (a, b) = (a.to_array(), b.to_array());
+    let ch = |i: usize| (a[i] as f32 + (b[i] as f32 - a[i] as f32) * t).round() as u8;
+    Color32::from_rgba_premultiplied(ch(0), ch(1), ch(2), ch(3))
+}
+
+impl Shade {
+    /// `color` for a cell of `layer` on row `row`.
+    pub fn color(&self, color: Color32, layer: Layer, row: usize) -> Color32 {
+        if layer == Layer::Effect {
+            return color;
+        }
+        let depth = row as f32 / self.rows.saturating_sub(1).max(1) as f32;
+        let dim = 1.0 - self.depth_dimming * depth;
+        let tinted = scale(color, self.tint.map(|t| t * dim));
+        if layer == Layer::Fish && self.glowing_fish && self.night > 0.0 {
+            mix(tinted, GLOW, self.night * 0.6)
+        } else {
+            tinted
+        }
+    }
+
+    /// The background fill (tinted, not dimmed).
+    pub fn background(&self, color: Color32) -> Color32 {
+        scale(color, self.tint)
+    }
+}
+
+/// Colour of the cell at (x, y) of `grid` under `theme` and an optional `shade`.
+pub fn shade_color(
+    grid: &CellGrid<'_>,
+    x: usize,
+    y: usize,
+    glyph: &str,
+    theme: &AsciiquariumTheme,
+    shade: Option<&Shade>,
+) -> Color32 {
+    let layer = grid.layer(x, y).unwrap_or_default();
+    let base = cell_color(glyph, layer, theme);
+    match shade {
+        Some(shade) => shade.color(base, layer, y),
+        None => base,
+    }
+}
+
+/// Cells of the moon's reflection on the waterline, if it is night and the moon is on.
+pub fn moon_reflection(state: &AquariumState) -> Vec<(isize, isize, &'static str)> {
+    let Some(cycle) = state.env.day_night.as_ref().filter(|c| c.moon) else {
+        return Vec::new();
+    };
+    let hour = cycle.hour(state.tick);
+    let (_, night) = tint_at(hour);
+    let (w, h) = state.size;
+    if night < 0.5 || w == 0 {
+        return Vec::new();
+    }
+    let progress = ((hour - MOONRISE).rem_euclid(24.0) / MOON_HOURS).min(1.0);
+    let cx = (progress * (w.saturating_sub(1)) as f32).round() as isize;
+    let mut out = Vec::new();
+    for (row, pattern) in MOON_ROWS.iter().enumerate().take(h) {
+        // Lower rows shimmer sideways.
+        let shimmer = if row == 0 {
+            0
+        } else {
+            ((state.tick / 6 + row as u64) % 3) as isize - 1
+        };
+        for (i, g) in pattern.char_indices() {
+            let x = cx - 1 + i as isize + shimmer;
+            if g != ' ' && x >= 0 && x < w as isize {
+                out.push((x, row as isize, &pattern[i..i + 1]));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycle_tints_and_dims() {
+        let (day, day_night) = tint_at(12.0);
+        assert_eq!((day, day_night), ([1.0, 1.0, 1.0], 0.0));
+        let (_, night) = tint_at(23.0);
+        assert_eq!(night, 1.0);
+        let (dawn, _) = tint_at(7.0);
+        assert!(dawn[0] > dawn[2]);
+        assert_eq!(hour_from_unix_seconds(86_400 + 3 * 3600, 60), 4.0);
+
+        let shade = Shade {
+            tint: [1.0, 1.0, 1.0],
+            night: 0.0,
+            depth_dimming: 0.5,
+            glowing_fish: true,
+            rows: 11,
+        };
+        let white = Color32::from_rgb(200, 200, 200);
+        assert_eq!(shade.color(white, Layer::Water, 0), white);
+        assert_eq!(
+            shade.color(white, Layer::Water, 10),
+            Color32::from_rgb(100, 100, 100)
+        );
+        assert_eq!(shade.color(white, Layer::Effect, 10), white);
+    }
+
+    #[test]
+    fn moon_reflects_only_at_night() {
+        let mut state = AquariumState {
+            size: (40, 12),
+            ..Default::default()
+        };
+        state.env.day_night = Some(DayNight {
+            clock: DayClock::Hour(12.0),
+            ..Default::default()
+        });
+        assert!(moon_reflection(&state).is_empty());
+        state.env.day_night = Some(DayNight {
+            clock: DayClock::Hour(24.5),
+            ..Default::default()
+        });
+        let cells = moon_reflection(&state);
+        assert!(cells.contains(&(20, 0, "*")));
+        state.env.decorations.clear();
+        let grid = crate::widgets::asciiquarium::render_aquarium_to_grid(&state, &[]);
+        assert_eq!(grid.layer(20, 0), Some(Layer::Effect));
+    }
+}
@@ -0,0 +1,299 @@
+/*!
+Decorations: the castle and other props resting in the tank.
+
+Agent Log:
+- Replaces the single `castle: bool`; `AquariumEnvironment::decorations` defaults to the
+  classic castle in the bottom-right corner.
+- Built-in kinds: castle (from the original), treasure chest, rocks, sunken ship, diver
+  helmet and aerator stone (hand-drawn here), plus custom art. The aerator is meant to carry a
+  `BubbleEmitter` (see `bubbles`).
+- Anchors say what a position is measured from, so props stay put sensibly on resize:
+  `Bottom` scales its column with the width, `Left`/`Right` keep their distance from that
+  wall, and `Free` is an absolute top-left cell. The first three rest on the floor, raised by
+  `position.1` rows.
+- Floor anchors rest on the seabed (the highest terrain under them) when there is one.
+- `depth` orders decorations; below `FOREGROUND_DEPTH` they are drawn behind seaweed and fish
+  (where the castle always was), from it upwards in front of fish.
+- An optional `DecorationAnimation` plays the kind's frames (the chest opens), releases
+  bubbles while fully open, then plays them back. Kinds with one frame only release bubbles.
+*/
+
+use super::asciiquarium::Transparency;
+use super::asciiquarium_assets::measure_art;
+use super::generated_assets as generated;
+use super::seabed::{floor_under, Seabed};
+
+/// Decorations with at least this depth are drawn in front of fish.
+pub const FOREGROUND_DEPTH: u8 = 128;
+/// Ticks each animation frame is shown while opening or closing.
+const FRAME_TICKS: u64 = 8;
+/// Ticks between bubbles while a decoration is open.
+const BUBBLE_GAP_TICKS: u64 = 6;
+
+const CHEST_FRAMES: &[&str] = &[
+    "\n  ______\n /______\\\n |__()__|",
+    "  ______\n /______\\\n |$$$$$$|\n |__()__|",
+    " |      |\n |______|\n |$$$$$$|\n |__()__|",
+];
+const ROCKS: &str = "     __\n  __/  \\_\n /  .    \\__\n/__________\\";
+const SUNKEN_SHIP: &str =
+    "          |\\\n          | \\\n   _______|__\\__\n   \\ o  o   o  /\n    \\__________/";
+const DIVER_HELMET: &str = "   ___\n _/   \\_\n| ( o ) |\n|_\\___/_|";
+const AERATOR_STONE: &str = " ____\n/####\\";
+
+/// What a decoration looks like.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecorationKind {
+    Castle,
+    TreasureChest,
+    Rocks,
+    SunkenShip,
+    DiverHelmet,
+    AeratorStone,
+    /// Host-supplied art (one frame).
+    Custom {
+        art: String,
+        transparency: Transparency,
+    },
+}
+
+impl DecorationKind {
+    /// Built-in animation frames; `None` for custom art, which has one frame it owns.
+    fn builtin_frames(&self) -> Option<&'static [&'static str]> {
+        Some(match self {
+            DecorationKind::Castle => &generated::CASTLE.frames[..1],
+            DecorationKind::TreasureChest => CHEST_FRAMES,
+            DecorationKind::Rocks => &[ROCKS],
+            DecorationKind::SunkenShip => &[SUNKEN_SHIP],
+            DecorationKind::DiverHelmet => &[DIVER_HELMET],
+            DecorationKind::AeratorStone => &[AERATOR_STONE],
+            DecorationKind::Custom { .. } => return None,
+        })
+    }
+
+    /// Number of animation frames (at least one).
+    pub fn frame_count(&self) -> usize {
+        self.builtin_frames().map_or(1, <[&str]>::len)
+    }
+
+    /// Art of animation frame `index`, clamped to the last frame; frame 0 is the resting one.
+    pub fn frame_art(&self, index: usize) -> &str {
+        match (self, self.builtin_frames()) {
+            (DecorationKind::Custom { art, .. }, _) => art,
+            (_, Some(frames)) => frames[index.min(frames.len() - 1)],
+            (_, None) => "",
+        }
+    }
+
+    pub fn transparency(&self) -> Transparency {
+        match self {
+            DecorationKind::Castle => generated::CASTLE.transparency,
+            DecorationKind::Custom { transparency, .. } => *transparency,
+            _ => Transparency::Auto,
+        }
+    }
+
+    /// Human-readable name, e.g. for tooltips.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DecorationKind::Castle => "Castle",
+            DecorationKind::TreasureChest => "Treasure chest",
+            DecorationKind::Rocks => "Rocks",
+            DecorationKind::SunkenShip => "Sunken ship",
+            DecorationKind::DiverHelmet => "Diver helmet",
+            DecorationKind::AeratorStone => "Aerator stone",
+            DecorationKind::Custom { .. } => "Decoration",
+        }
+    }
+
+    /// Size in cells (the largest frame).
+    pub fn size(&self) -> (usize, usize) {
+        (0..self.frame_count())
+            .map(|i| measure_art(self.frame_art(i)))
+            .fold((1, 1), |a, b| (a.0.max(b.0), a.1.max(b.1)))
+    }
+}
+
+/// What `Decoration::position` is measured from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecorationAnchor {
+    /// On the floor; `position.0` is the left column and scales with the tank width.
+    Bottom,
+    /// On the floor, `position.0` cells in from the left wall.
+    Left,
+    /// On the floor, `position.0` cells in from the right wall.
+    Right,
+    /// Top-left cell at `position`.
+    Free,
+}
+
+/// Periodic open/close animation with bubbles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecorationAnimation {
+    /// Ticks between the starts of two cycles.
+    pub period: u64,
+    /// Bubbles released per cycle while fully open.
+    pub bubbles: usize,
+}
+
+/// A prop in the tank.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decoration {
+    pub kind: DecorationKind,
+    pub anchor: DecorationAnchor,
+    /// Column (see `anchor`) and rows raised above the floor; the top-left cell for `Free`.
+    pub position: (usize, usize),
+    /// Draw order; see `FOREGROUND_DEPTH`.
+    pub depth: u8,
+    pub animation: Option<DecorationAnimation>,
+}
+
+impl Decoration {
+    /// A still decoration resting on the floor at column `x`.
+    pub fn new(kind: DecorationKind, x: usize) -> Self {
+        Self {
+            kind,
+            anchor: DecorationAnchor::Bottom,
+            position: (x, 0),
+            depth: 0,
+            animation: None,
+        }
+    }
+
+    /// The classic castle, one cell in from the bottom-right corner.
+    pub fn castle() -> Self {
+        Self {
+            anchor: DecorationAnchor::Right,
+            position: (1, 0),
+            ..Self::new(DecorationKind::Castle, 0)
+        }
+    }
+
+    /// A treasure chest at column `x` that opens every 300 ticks and releases 5 bubbles.
+    pub fn treasure_chest(x: usize) -> Self {
+        Self {
+            animation: Some(DecorationAnimation {
+                period: 300,
+                bubbles: 5,
+            }),
+            ..Self::new(DecorationKind::TreasureChest, x)
+        }
+    }
+
+    pub fn with_anchor(mut self, anchor: DecorationAnchor, position: (usize, usize)) -> Self {
+        self.anchor = anchor;
+        self.position = position;
+        self
+    }
+
+    pub fn with_depth(mut self, depth: u8) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    pub fn with_animation(mut self, animation: Option<DecorationAnimation>) -> Self {
+        self.animation = animation;
+        self
+    }
+
+    pub fn in_front(&self) -> bool {
+        self.depth >= FOREGROUND_DEPTH
+    }
+
+    /// Top-left cell in a tank of `size`, resting on `seabed` if there is one.
+    pub fn origin(&self, size: (usize, usize), seabed: Option<&Seabed>) -> (isize, isize) {
+        let (dw, dh) = self.kind.size();
+        let x = match self.anchor {
+            DecorationAnchor::Bottom | DecorationAnchor::Left | DecorationAnchor::Free => {
+                self.position.0 as isize
+            }
+            DecorationAnchor::Right => size.0 as isize - dw as isize - self.position.0 as isize,
+        };
+        if self.anchor == DecorationAnchor::Free {
+            return (x, self.position.1 as isize);
+        }
+        let floor = floor_under(seabed, x, dw, size) as isize;
+        (x, floor - dh as isize - self.position.1 as isize)
+    }
+
+    /// Set `position` so the top-left cell lands on `origin` (already inside the tank).
+    pub fn set_origin(
+        &mut self,
+        origin: (usize, usize),
+        size: (usize, usize),
+        seabed: Option<&Seabed>,
+    ) {
+        let (dw, dh) = self.kind.size();
+        let floor = floor_under(seabed, origin.0 as isize, dw, size);
+        let lift = floor.saturating_sub(dh).saturating_sub(origin.1);
+        self.position = match self.anchor {
+            DecorationAnchor::Bottom | DecorationAnchor::Left => (origin.0, lift),
+            DecorationAnchor::Right => (size.0.saturating_sub(dw + origin.0), lift),
+            DecorationAnchor::Free => origin,
+        };
+    }
+
+    /// Ticks the animation spends opening (or closing) and fully open.
+    fn phases(&self, animation: &DecorationAnimation) -> (u64, u64) {
+        let opening = (self.kind.frame_count() as u64 - 1) * FRAME_TICKS;
+        let open = animation.bubbles as u64 * BUBBLE_GAP_TICKS + FRAME_TICKS;
+        (opening, open)
+    }
+
+    /// Frame shown at `tick`.
+    pub fn frame(&self, tick: u64) -> usize {
+        let Some(animation) = &self.animation else {
+            return 0;
+        };
+        let last = self.kind.frame_count() - 1;
+        let (opening, open) = self.phases(animation);
+        let p = tick % animation.period.max(1);
+        if p < opening {
+            (p / FRAME_TICKS) as usize
+        } else if p < opening + open {
+            last
+        } else if p < 2 * opening + open {
+            last.saturating_sub(1 + ((p - opening - open) / FRAME_TICKS) as usize)
+        } else {
+            0
+        }
+    }
+
+    /// Whether a bubble leaves the decoration at `tick`.
+    pub fn releases_bubble(&self, tick: u64) -> bool {
+        let Some(animation) = &self.animation else {
+            return false;
+        };
+        let (opening, _) = self.phases(animation);
+        let p = tick % animation.period.max(1);
+        p >= opening
+            && p < opening + animation.bubbles as u64 * BUBBLE_GAP_TICKS
+            && (p - opening) % BUBBLE_GAP_TICKS == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchors_place_and_chest_opens() {
+        let castle = Decoration::castle();
+        let (cw, ch) = DecorationKind::Castle.size();
+        assert_eq!(
+            castle.origin((80, 24), None),
+            (80 - cw as isize - 1, 24 - ch as isize)
+        );
+
+        let mut rocks =
+            Decoration::new(DecorationKind::Rocks, 0).with_anchor(DecorationAnchor::Right, (0, 0));
+        rocks.set_origin((50, 10), (80, 24), None);
+        assert_eq!(rocks.origin((80, 24), None), (50, 10));
+
+        let chest = Decoration::treasure_chest(3);
+        let frames: Vec<usize> = (0..80).step_by(8).map(|t| chest.frame(t)).collect();
+        assert_eq!(frames, vec![0, 1, 2, 2, 2, 2, 2, 1, 0, 0]);
+        let bubbles = (0..300).filter(|&t| chest.releases_bubble(t)).count();
+        assert_eq!(bubbles, 5);
+    }
+}
@@ -0,0 +1,247 @@
+/*!
+Editing helpers: move, remove and add entities by cell position.
+
+Agent Log:
+- Added for the widget's edit mode; pure functions over `AquariumState`, no egui, so hosts
+  can drive them from their own tools too.
+- Origins are the top-left cell the entity is anchored at (before bobbing), matching what
+  `placed_sprites` draws from.
+- Moves are clamped so the entity stays inside the tank. Seaweed keeps its root on the floor
+  and only moves sideways; ships stay on the waterline; decorations keep their anchor.
+- Removing a stalk turns `auto_seaweed` off and removing a visitor pushes its next spawn back,
+  so the tank does not immediately undo the edit.
+- Removing a fish, stalk or decoration shifts later indices down, like `Vec::remove`; bubble
+  emitters on a removed stalk or decoration go with it.
+*/
+
+use super::asciiquarium::{
+    placed_sprites, seaweed_top, AquariumState, EntityRef, FishArt, FishBehavior, FishInstance,
+};
+use super::asciiquarium_assets::measure_art;
+use super::bubbles::detach_emitters;
+
+/// Speed (cells per second) of fish added with `add_fish_at`: 2.5..12.5, like re-seeded fish.
+const ADDED_MIN_SPEED: f32 = 2.5;
+
+/// Top-left cell `entity` is anchored at, if it exists.
+pub fn entity_origin(state: &AquariumState, entity: EntityRef) -> Option<(isize, isize)> {
+    let floor = |x: f32| x.floor() as isize;
+    match entity {
+        EntityRef::Fish(i) => state
+            .fishes
+            .get(i)
+            .map(|f| (floor(f.position.0), floor(f.position.1))),
+        EntityRef::Seaweed(i) => state
+            .env
+            .seaweed
+            .get(i)
+            .map(|s| (s.x as isize, seaweed_top(state, s) as isize)),
+        EntityRef::Decoration(i) => state
+            .env
+            .decorations
+            .get(i)
+            .map(|d| d.origin(state.size, state.env.seabed.as_ref())),
+        EntityRef::Ship(i) => state.env.ships.get(i).map(|s| (floor(s.x), s.y as isize)),
+        EntityRef::Shark(i) => state.env.sharks.get(i).map(|s| (floor(s.x), s.y as isize)),
+        EntityRef::Whale(i) => state.env.whales.get(i).map(|s| (floor(s.x), s.y as isize)),
+    }
+}
+
+/// Size in cells of the art drawn for `entity` (1x1 if it has none).
+fn entity_size(state: &AquariumState, assets: &[FishArt], entity: EntityRef) -> (usize, usize) {
+    let size = match entity {
+        EntityRef::Fish(i) => state
+            .fishes
+            .get(i)
+            .and_then(|f| assets.get(f.fish_art_index))
+            .map(|a| (a.width, a.height)),
+        EntityRef::Seaweed(i) => state.env.seaweed.get(i).map(|s| (2, s.height)),
+        EntityRef::Decoration(i) => state.env.decorations.get(i).map(|d| d.kind.size()),
+        EntityRef::Ship(_) | EntityRef::Shark(_) | EntityRef::Whale(_) => {
+            placed_sprites(state, assets)
+                .into_iter()
+                .find(|s| s.entity == entity)
+                .map(|s| measure_art(s.art))
+        }
+    };
+    size.unwrap_or((1, 1))
+}
+
+/// Move `entity` so its top-left cell is at `origin`, clamped to the tank.
+/// Returns false if the entity does not exist.
+pub fn move_entity(
+    state: &mut AquariumState,
+    assets: &[FishArt],
+    entity: EntityRef,
+    origin: (isize, isize),
+) -> bool {
+    let (ew, eh) = entity_size(state, assets, entity);
+    let (w, h) = state.size;
+    let x = origin.0.clamp(0, w.saturating_sub(ew) as isize) as usize;
+    let y = origin.1.clamp(0, h.saturating_sub(eh) as isize) as usize;
+    match entity {
+        EntityRef::Fish(i) => {
+            let Some(fish) = state.fishes.get_mut(i) else {
+                return false;
+            };
+            fish.position = (x as f32, y as f32);
+        }
+        EntityRef::Seaweed(i) => {
+            let Some(stalk) = state.env.seaweed.get_mut(i) else {
+                return false;
+            };
+            stalk.x = x;
+        }
+        EntityRef::Decoration(i) => {
+            let Some(decoration) = state.env.decorations.get_mut(i) else {
+                return false;
+            };
+            decoration.set_origin((x, y), (w, h), state.env.seabed.as_ref());
+        }
+        EntityRef::Ship(i) => {
+            let Some(ship) = state.env.ships.get_mut(i) else {
+                return false;
+            };
+            ship.x = x as f32;
+        }
+        EntityRef::Shark(i) => {
+            let Some(shark) = state.env.sharks.get_mut(i) else {
+                return false;
+            };
+            (shark.x, shark.y) = (x as f32, y);
+        }
+        EntityRef::Whale(i) => {
+            let Some(whale) = state.env.whales.get_mut(i) else {
+                return false;
+            };
+            (whale.x, whale.y) = (x as f32, y);
+        }
+    }
+    true
+}
+
+/// Remove `entity` from the tank. Returns false if it does not exist.
+pub fn remove_entity(state: &mut AquariumState, entity: EntityRef) -> bool {
+    let tick = state.tick;
+    let env = &mut state.env;
+    match entity {
+        EntityRef::Fish(i) if i < state.fishes.len() => {
+            state.fishes.remove(i);
+            if i < state.fish_behaviors.len() {
+                state.fish_behaviors.remove(i);
+            }
+            if i < state.fish_cruises.len() {
+                state.fish_cruises.remove(i);
+            }
+        }
+        EntityRef::Seaweed(i) if i < env.seaweed.len() => {
+            env.seaweed.remove(i);
+            env.auto_seaweed = false;
+            detach_emitters(&mut env.emitters, entity);
+        }
+        EntityRef::Decoration(i) if i < env.decorations.len() => {
+            env.decorations.remove(i);
+            detach_emitters(&mut env.emitters, entity);
+        }
+        // Same delays as when a visitor leaves the tank on its own.
+        EntityRef::Ship(i) if i < env.ships.len() => {
+            env.ships.remove(i);
+            env.next_ship_spawn = tick + 600;
+        }
+        EntityRef::Shark(i) if i < env.sharks.len() => {
+            env.sharks.remove(i);
+            env.next_shark_spawn = tick + 900;
+        }
+        EntityRef::Whale(i) if i < env.whales.len() => {
+            env.whales.remove(i);
+            env.next_whale_spawn = tick + 1200;
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Add a Normal fish using `assets[asset_index]`, centred on `cell` and clamped to the tank,
+/// swimming at a seeded cruising speed. Returns its index in `state.fishes`.
+pub fn add_fish_at(
+    state: &mut AquariumState,
+    assets: &[FishArt],
+    asset_index: usize,
+    cell: (usize, usize),
+) -> Option<usize> {
+    let art = assets.get(asset_index)?;
+    let (w, h) = state.size;
+    let x = (cell.0.saturating_sub(art.width / 2)).min(w.saturating_sub(art.width));
+    let y = (cell.1.saturating_sub(art.height / 2)).min(h.saturating_sub(art.height));
+    let seed = (state.tick ^ ((state.fishes.len() as u64) << 32))
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    let speed = ADDED_MIN_SPEED + ((seed >> 8) % 100) as f32 / 10.0;
+    let vx = if (seed >> 40) & 1 == 0 { speed } else { -speed };
+    state
+        .fish_behaviors
+        .resize(state.fishes.len(), FishBehavior::Normal);
+    state.fish_cruises.resize(state.fishes.len(), None);
+    state.fishes.push(FishInstance {
+        fish_art_index: asset_index,
+        position: (x as f32, y as f32),
+        velocity: (vx, 0.0),
+    });
+    state.fish_behaviors.push(FishBehavior::Normal);
+    state.fish_cruises.push(None);
+    Some(state.fishes.len() - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::asciiquarium::{seaweed_cells, Seaweed};
+    use crate::widgets::seabed::Seabed;
+
+    #[test]
+    fn move_clamps_and_remove_sticks() {
+        let assets = vec![FishArt::new("<><")];
+        let mut state = AquariumState {
+            size: (20, 10),
+            ..Default::default()
+        };
+        let i = add_fish_at(&mut state, &assets, 0, (19, 9)).unwrap_or(usize::MAX);
+        assert_eq!(state.fishes[i].position, (17.0, 9.0));
+        assert!(move_entity(
+            &mut state,
+            &assets,
+            EntityRef::Fish(i),
+            (-4, 3)
+        ));
+        assert_eq!(entity_origin(&state, EntityRef::Fish(i)), Some((0, 3)));
+
+        state.env.seaweed.push(Seaweed {
+            x: 5,
+            height: 4,
+            sway_phase: 0,
+        });
+        assert!(move_entity(
+            &mut state,
+            &assets,
+            EntityRef::Seaweed(0),
+            (30, 0)
+        ));
+        assert_eq!(entity_origin(&state, EntityRef::Seaweed(0)), Some((18, 6)));
+        // On a seabed the stalk grows from the terrain, as drawn.
+        state.env.seabed = Some(Seabed::new(7).with_max_height(3));
+        let top = seaweed_cells(&state, 0, &state.env.seaweed[0])
+            .iter()
+            .map(|&(_, y, _)| y)
+            .min();
+        assert!(top < Some(6));
+        assert_eq!(
+            entity_origin(&state, EntityRef::Seaweed(0)).map(|o| o.1),
+            top
+        );
+        assert!(remove_entity(&mut state, EntityRef::Seaweed(0)));
+        assert!(!state.env.auto_seaweed);
+        assert!(remove_entity(&mut state, EntityRef::Fish(i)));
+        assert!(state.fishes.is_empty() && state.fish_behaviors.is_empty());
+        assert!(!remove_entity(&mut state, EntityRef::Fish(0)));
+    }
+}
@@ -0,0 +1,208 @@
+/*!
+Food: pellets dropped on the surface that sink, draw nearby fish and get eaten.
+
+Agent Log:
+- Added for feeding the tank from outside (the `stream` control messages); the widgets have
+  no food button of their own. `drop_food` scatters a few pellets just below the water band
+  (`weather::surface_row`) and
+  `update_food` (called from `update_aquarium`, before fish move) sinks them and steers fish.
+- Pellets sink at `SINK_SPEED`, drift with the current (`current_at`) and come to rest on
+  the floor (`floor_row`); uneaten ones are gone after `FOOD_TICKS`.
+- Normal fish within `FOOD_SIGHT` of a pellet turn toward the nearest one, vertically at
+  cruising speed, so they never dart; transit fish ignore food. A pellet inside a fish's box
+  is eaten. A chasing fish keeps the velocity it had in `Cruise::Feeding` and goes back to it
+  (heading and level swimming) once none is in sight; darting fish ignore food.
+- Pellet spread and placement are deterministic (tick-seeded), like the rest of the core.
+*/
+
+use super::asciiquarium::{
+    floor_row, AquariumState, Cruise, FishArt, FishBehavior, CLASSIC_DT, CRUISE_MAX_VY,
+};
+use super::current::current_at;
+use super::weather::surface_row;
+
+/// Pellets dropped by `drop_food`.
+pub const FEED_PELLETS: usize = 5;
+/// Ticks a pellet lasts if no fish eats it (about 20 seconds).
+pub const FOOD_TICKS: u32 = 600;
+/// Cells from a fish's centre within which it notices a pellet.
+pub const FOOD_SIGHT: f32 = 12.0;
+/// Sinking speed in cells per second.
+const SINK_SPEED: f32 = 1.5;
+const GLYPH: &str = ",";
+
+/// A sinking food pellet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pellet {
+    pub position: (f32, f32),
+    /// Ticks since it was dropped.
+    pub age: u32,
+}
+
+/// Scatter `count` pellets over a few columns around `x`, just below the lowest water line.
+pub fn drop_food(state: &mut AquariumState, x: usize, count: usize) {
+    let (w, _) = state.size;
+    let kind = state.env.weather.kind;
+    let phase = state.env.water_phase;
+    for i in 0..count {
+        let seed = (state.tick ^ ((i as u64) << 24))
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let spread = ((seed >> 33) % 7) as isize - 3;
+        let px = (x as isize + spread).clamp(0, w.saturating_sub(1) as isize) as usize;
+        let surface = surface_row(kind, phase, px) as f32;
+        state.env.food.push(Pellet {
+            // Staggered a little so the pellets do not sink in a line.
+            position: (px as f32, surface + 1.0 + ((seed >> 40) % 3) as f32 * 0.3),
+            age: 0,
+        });
+    }
+}
+
+/// Sink and age the pellets, steer fish toward them and let fish eat them.
+pub fn update_food(state: &mut AquariumState, assets: &[FishArt]) {
+    let mut food = std::mem::take(&mut state.env.food);
+    for pellet in &mut food {
+        pellet.age += 1;
+        let (cx, cy) = current_at(state, pellet.position);
+        let floor = floor_row(state, pellet.position.0.floor() as isize, 1) as f32 - 1.0;
+        pellet.position.0 += cx * CLASSIC_DT;
+        pellet.position.1 = (pellet.position.1 + (SINK_SPEED + cy) * CLASSIC_DT).min(floor);
+    }
+    food.retain(|p| p.age < FOOD_TICKS);
+
+    state.fish_cruises.resize(state.fishes.len(), None);
+    let cruises = state.fish_cruises.iter_mut();
+    for (i, (fish, cruise)) in state.fishes.iter_mut().zip(cruises).enumerate() {
+        if state.fish_behaviors.get(i) == Some(&FishBehavior::Transit) {
+            continue;
+        }
+        let (fw, fh) = assets
+            .get(fish.fish_art_index)
+            .map(|a| (a.width as f32, a.height as f32))
+            .unwrap_or((1.0, 1.0));
+        let (x0, y0) = fish.position;
+        // Eat what is inside the fish's box.
+        food.retain(|p| {
+            let (px, py) = (p.position.0.floor(), p.position.1.floor());
+            !(px >= x0.floor() && px < x0 + fw && py >= y0.floor() && py < y0 + fh)
+        });
+        // Scared fish ignore food until they have calmed down.
+        if matches!(cruise, Some(Cruise::Darting(_))) {
+            continue;
+        }
+        let centre = (x0 + fw / 2.0, y0 + fh / 2.0);
+        let nearest = food
+            .iter()
+            .map(|p| (p.position.0 - centre.0, p.position.1 - centre.1))
+            .filter(|(dx, dy)| dx.hypot(*dy) <= FOOD_SIGHT)
+            .min_by(|a, b| a.0.hypot(a.1).total_cmp(&b.0.hypot(b.1)));
+        let Some((dx, dy)) = nearest else {
+            // Nothing in sight: back to how it swam before.
+            if let Some(Cruise::Feeding(velocity)) = cruise.take() {
+                fish.velocity = velocity;
+            }
+            continue;
+        };
+        cruise.get_or_insert(Cruise::Feeding(fish.velocity));
+        if dx.abs() >= fw / 2.0 {
+            fish.velocity.0 = fish.velocity.0.abs() * dx.signum();
+        }
+        fish.velocity.1 = if dy.abs() < 0.5 {
+            0.0
+        } else {
+            dy.signum() * CRUISE_MAX_VY
+        };
+    }
+    state.env.food = food;
+}
+
+/// Cells of the pellets, on the grid or not.
+pub fn food_cells(state: &AquariumState) -> Vec<(isize, isize, &'static str)> {
+    state
+        .env
+        .food
+        .iter()
+        .map(|p| {
+            (
+                p.position.0.floor() as isize,
+                p.position.1.floor() as isize,
+                GLYPH,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::asciiquarium::FishInstance;
+    use crate::widgets::weather::WeatherKind;
+
+    #[test]
+    fn pellets_sink_to_the_floor_and_get_eaten() {
+        let assets = vec![FishArt::new("<><")];
+        let mut state = AquariumState {
+            size: (40, 16),
+            ..Default::default()
+        };
+        state.env.weather.kind = WeatherKind::Storm;
+        drop_food(&mut state, 10, FEED_PELLETS);
+        assert_eq!(state.env.food.len(), FEED_PELLETS);
+        assert!(state
+            .env
+            .food
+            .iter()
+            .all(|p| (7.0..=13.0).contains(&p.position.0)));
+        // Below the water band, wave dip included.
+        assert!(state.env.food.iter().all(|p| {
+            let surface = surface_row(WeatherKind::Storm, 0, p.position.0 as usize);
+            p.position.1.floor() as usize == surface + 1
+        }));
+        state.env.weather.kind = WeatherKind::Fair;
+        for _ in 0..400 {
+            update_food(&mut state, &assets);
+        }
+        // Resting on the bottom row, not through it.
+        assert!(state.env.food.iter().all(|p| p.position.1 == 15.0));
+        assert!(food_cells(&state)
+            .iter()
+            .all(|&(_, y, g)| y == 15 && g == ","));
+
+        // A fish nearby turns toward the food and eats the pellet it reaches.
+        let x = state
+            .env
+            .food
+            .iter()
+            .map(|p| p.position.0)
+            .fold(0.0, f32::max);
+        state.fishes.push(FishInstance {
+            fish_art_index: 0,
+            position: (x + 4.0, 13.0),
+            velocity: (3.0, 0.0),
+        });
+        // A scared fish close by keeps darting.
+        state.fishes.push(FishInstance {
+            fish_art_index: 0,
+            position: (x + 6.0, 12.0),
+            velocity: (45.0, 2.4),
+        });
+        state.fish_behaviors = vec![FishBehavior::Normal; 2];
+        state.fish_cruises = vec![None, Some(Cruise::Darting((3.0, 0.0)))];
+        update_food(&mut state, &assets);
+        assert!(state.fishes[0].velocity.0 < 0.0 && state.fishes[0].velocity.1 > 0.0);
+        assert_eq!(state.fish_cruises[0], Some(Cruise::Feeding((3.0, 0.0))));
+        assert_eq!(state.fishes[1].velocity, (45.0, 2.4));
+        state.fishes[0].position = (x - 1.0, 15.0);
+        let before = state.env.food.len();
+        update_food(&mut state, &assets);
+        assert!(state.env.food.len() < before);
+        assert!(state.env.food.iter().all(|p| p.position.0 < x - 1.0));
+
+        // With no food in sight it swims on as before: same heading, level again.
+        state.env.food.clear();
+        update_food(&mut state, &assets);
+        assert_eq!(state.fishes[0].velocity, (3.0, 0.0));
+        assert_eq!(state.fish_cruises[0], None);
+    }
+}
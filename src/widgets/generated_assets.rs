@@ -0,0 +1,308 @@
+// AUTO-GENERATED FILE: Do not edit by hand.
+//
+// Generated by `src/extract.rs` (via `extract_fish` or the `build-assets` build script)
+// from the original Perl Asciiquarium script. Every creature's ASCII art and colour
+// masks as constants, plus a helper to build Vec<FishArt> for the widget.
+
+use super::asciiquarium::{Facing, FishArt, Transparency};
+use super::asciiquarium_assets::GeneratedSprite;
+
+pub const FISH_0001: &str = "   \\\n  / \\\n>=_('>\n  \\_/\n   /";
+pub const FISH_MASK_0001: &str = "   1\n  1 1\n663745\n  111\n   3";
+pub const FISH_0002: &str = "  /\n / \\\n<')_=<\n \\_/\n  \\";
+pub const FISH_MASK_0002: &str = "  2\n 111\n547366\n 111\n  3";
+pub const FISH_0003: &str = "     ,\n     }\\\n\\  .'  `\\\n}}<   ( 6>\n/  `,  .'\n     }/\n     '";
+pub const FISH_MASK_0003: &str = "     2\n     22\n6  11  11\n661   7 45\n6  11  11\n     33\n     3";
+pub const FISH_0004: &str = "    ,\n   /{\n /'  `.  /\n<6 )   >{{\n `.  ,'  \\\n   \\{\n    `";
+pub const FISH_MASK_0004: &str = "    2\n   22\n 11  11  6\n54 7   166\n 11  11  6\n   33\n    3";
+pub const FISH_0005: &str = "            \\'`.\n             )  \\\n(`.??????_.-`' ' '`-.\n \\ `.??.`        (o) \\_\n  >  ><     (((       (\n / .`??`._      /_|  /'\n(.`???????`-. _  _.-`\n            /__/'";
+pub const FISH_MASK_0005: &str = "            1111\n             1  1\n111      11111 1 1111\n 1 11  11        141 11\n  1  11     777       5\n 1 11  111      333  11\n111       111 1  1111\n            11111";
+pub const FISH_0006: &str = "       .'`/\n      /  (\n  .-'` ` `'-._??????.')\n_/ (o)        '.??.' /\n)       )))     ><  <\n`\\  |_\\      _.'??'. \\\n  '-._  _ .-'???????'.)\n      `\\__\\";
+pub const FISH_MASK_0006: &str = "       1111\n      1  1\n  1111 1 11111      111\n11 141        11  11 1\n5       777     11  1\n11  333      111  11 1\n  1111  1 111       111\n      11111";
+pub const FISH_0007: &str = "       ,--,_\n__    _\\.---'-.\n\\ '.-\"     // o\\\n/_.'-._    \\\\  /\n       `\"--(/\"`";
+pub const FISH_MASK_0007: &str = "       22222\n66    121111211\n6 6111     77 41\n6661111    77  1\n       11113311";
+pub const FISH_0008: &str = "    _,--,\n .-'---./_    __\n/o \\\\     \"-.' /\n\\  //    _.-'._\\\n `\"\\)--\"`";
+pub const FISH_MASK_0008: &str = "    22222\n 112111121    66\n14 77     1116 6\n1  77    1111666\n 11331111";
+pub const FISH_0009: &str = "       \\\n     ...\\..,\n\\  /'       \\\n >=     (  ' >\n/  \\      / /\n    `\"'\"'/''";
+pub const FISH_MASK_0009: &str = "       2\n     1112111\n6  11       1\n 66     7  4 5\n6  1      3 1\n    11111311";
+pub const FISH_0010: &str = "      /\n  ,../...\n /       '\\  /\n< '  )     =<\n \\ \\      /  \\\n  `'\\'\"'\"'";
+pub const FISH_MASK_0010: &str = "      2\n  1112111\n 1       11  6\n5 4  7     66\n 1 3      1  6\n  11311111";
+pub const FISH_0011: &str = "    \\\n\\ /--\\\n>=  (o>\n/ \\__/\n    /";
+pub const FISH_MASK_0011: &str = "    2\n6 1111\n66  745\n6 1111\n    3";
+pub const FISH_0012: &str = "  /\n /--\\ /\n<o)  =<\n \\__/ \\\n  \\";
+pub const FISH_MASK_0012: &str = "  2\n 1111 6\n547  66\n 1111 6\n  3";
+pub const FISH_0013: &str = "       \\:.\n\\;,   ,;\\\\\\,,\n  \\\\\\;;:::::::o\n  ///;;::::::::<\n /;` ``/////``";
+pub const FISH_MASK_0013: &str = "       222\n666   1122211\n  6661111111114\n  66611111111115\n 666 113333311";
+pub const FISH_0014: &str = "      .:/\n   ,,///;,   ,;/\n o:::::::;;///\n>::::::::;;\\\\\\\n  ''\\\\\\\\\\'' ';\\";
+pub const FISH_MASK_0014: &str = "      222\n   1122211   666\n 4111111111666\n51111111111666\n  113333311 666";
+pub const FISH_0015: &str = "  __\n><_'>\n   '";
+pub const FISH_MASK_0015: &str = "  11\n61145\n   3";
+pub const FISH_0016: &str = " __\n<'_><\n `";
+pub const FISH_MASK_0016: &str = " 11\n54116\n 3";
+pub const FISH_0017: &str = "   ..\\,\n>='   ('>\n  '''/''";
+pub const FISH_MASK_0017: &str = "   1121\n661   745\n  111311";
+pub const FISH_0018: &str = "  ,/..\n<')   `=<\n ``\\```";
+pub const FISH_MASK_0018: &str = "  1211\n547   166\n 113111";
+pub const FISH_0019: &str = "   \\\n  / \\\n>=_('>\n  \\_/\n   /";
+pub const FISH_MASK_0019: &str = "   2\n  1 1\n661745\n  111\n   3";
+pub const FISH_0020: &str = "  /\n / \\\n<')_=<\n \\_/\n  \\";
+pub const FISH_MASK_0020: &str = "  2\n 1 1\n547166\n 111\n  3";
+pub const FISH_0021: &str = "  ,\\\n>=('>\n  '/";
+pub const FISH_MASK_0021: &str = "  12\n66745\n  13";
+pub const FISH_0022: &str = " /,\n<')=<\n \\`";
+pub const FISH_MASK_0022: &str = " 21\n54766\n 31";
+pub const FISH_0023: &str = "  __\n\\/ o\\\n/\\__/";
+pub const FISH_MASK_0023: &str = "  11\n61 41\n61111";
+pub const FISH_0024: &str = " __\n/o \\/\n\\__/\\";
+pub const FISH_MASK_0024: &str = " 11\n14 16\n11116";
+
+pub const SHARK_RIGHT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "                              __\n                             ( `\\\n  ,??????????????????????????)   `\\\n;' `.????????????????????????(     `\\__\n ;   `.?????????????__..---''          `~~~~-._\n  `.   `.____...--''                       (b  `--._\n    >                     _.-'      .((      ._     )\n  .`.-`--...__         .-'     -.___.....-(|/|/|/|/'\n ;.'?????????`. ...----`.___.',,,_______......---'\n '???????????'-'",
+    ],
+    masks: &[
+        "\n\n\n\n\n                                           cR\n \n                                          cWWWWWWWW",
+    ],
+    transparency: Transparency::Auto,
+};
+
+pub const SHARK_LEFT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "                     __\n                    /' )\n                  /'   (??????????????????????????,\n              __/'     )????????????????????????.' `;\n      _.-~~~~'          ``---..__?????????????.'   ;\n _.--'  b)                       ``--...____.'   .'\n(     _.      )).      `-._                     <\n `\\|\\|\\|\\|)-.....___.-     `-.         __...--'-.'.\n   `---......_______,,,`.___.'----... .'?????????`.;\n                                     `-`???????????`",
+    ],
+    masks: &[
+        "\n\n\n\n\n        Rc\n\n  WWWWWWWWc",
+    ],
+    transparency: Transparency::Auto,
+};
+
+pub const WHALE_RIGHT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "        .-----:\n      .'       `.\n,????/       (o) \\\n\\`._/          ,__)",
+    ],
+    masks: &[
+        "             C C\n           CCCCCCC\n           C  C  C\n        BBBBBBB\n      BB       BB\nB    B       BWB B\nBBBBB          BBBB",
+    ],
+    transparency: Transparency::Auto,
+};
+
+pub const WHALE_LEFT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "    :-----.\n  .'       `.\n / (o)       \\????,\n(__,          \\_.'/",
+    ],
+    masks: &[
+        "   C C\n CCCCCCC\n C  C  C\n    BBBBBBB\n  BB       BB\n B BWB       B    B\nBBBB          BBBBB",
+    ],
+    transparency: Transparency::Auto,
+};
+
+pub const WATER_SPOUT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "\n\n   :",
+        "\n   :\n   :",
+        "  . .\n  -:-\n   :",
+        "  . .\n .-:-.\n   :",
+        "  . .\n'.-:-.`\n'  :  '",
+        "\n .- -.\n;  :  ;",
+        "\n\n;     ;",
+    ],
+    masks: &[],
+    transparency: Transparency::Auto,
+};
+
+pub const SHIP_RIGHT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "     |    |    |\n    )_)  )_)  )_)\n   )___))___))___)\\\n  )____)____)_____)\\\\\n_____|____|____|____\\\\\\__\n\\                   /",
+    ],
+    masks: &[
+        "     y    y    y\n\n                  w\n                   ww\nyyyyyyyyyyyyyyyyyyyywwwyy\ny                   y",
+    ],
+    transparency: Transparency::Auto,
+};
+
+pub const SHIP_LEFT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "         |    |    |\n        (_(  (_(  (_(\n      /(___((___((___(\n    //(_____(____(____(\n__///____|____|____|_____\n    \\                   /",
+    ],
+    masks: &[
+        "         y    y    y\n\n      w\n    ww\nyywwwyyyyyyyyyyyyyyyyyyyy\n    y                   y",
+    ],
+    transparency: Transparency::Auto,
+};
+
+pub const NEW_MONSTER_RIGHT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "         _???_?????????????????????_???_???????_a_a\n       _{.`=`.}_??????_???_??????_{.`=`.}_????{/ ''\\_\n _????{.'  _  '.}????{.`'`.}????{.'  _  '.}??{|  ._oo)\n{ \\??{/  .'?'.  \\}??{/ .-. \\}??{/  .'?'.  \\}?{/  |",
+        "                      _???_????????????????????_a_a\n  _??????_???_??????_{.`=`.}_??????_???_??????{/ ''\\_\n { \\????{.`'`.}????{.'  _  '.}????{.`'`.}????{|  ._oo)\n  \\ \\??{/ .-. \\}??{/  .'?'.  \\}??{/ .-. \\}???{/  |",
+    ],
+    masks: &[
+        "                                                W W",
+    ],
+    transparency: Transparency::Auto,
+};
+
+pub const NEW_MONSTER_LEFT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "   a_a_???????_???_?????????????????????_???_\n _/'' \\}????_{.`=`.}_??????_???_??????_{.`=`.}_\n(oo_.  |}??{.'  _  '.}????{.`'`.}????{.'  _  '.}????_\n    |  \\}?{/  .'?'.  \\}??{/ .-. \\}??{/  .'?'.  \\}??/ }",
+        "   a_a_????????????????????_   _\n _/'' \\}??????_???_??????_{.`=`.}_??????_???_??????_\n(oo_.  |}????{.`'`.}????{.'  _  '.}????{.`'`.}????/ }\n    |  \\}???{/ .-. \\}??{/  .'?'.  \\}??{/ .-. \\}??/ /",
+    ],
+    masks: &[
+        "   W W",
+    ],
+    transparency: Transparency::Auto,
+};
+
+pub const OLD_MONSTER_RIGHT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "                                                          ____\n            __??????????????????????????????????????????/   o  \\\n          /    \\????????_?????????????????????_???????/     ____ >\n  _??????|  __  |?????/   \\????????_????????/   \\????|     |\n | \\?????|  ||  |????|     |?????/   \\?????|     |???|     |",
+        "                                                          ____\n                                             __?????????/   o  \\\n             _?????????????????????_???????/    \\?????/     ____ >\n   _???????/   \\????????_????????/   \\????|  __  |???|     |\n  | \\?????|     |?????/   \\?????|     |???|  ||  |???|     |",
+        "                                                          ____\n                                  __????????????????????/   o  \\\n _??????????????????????_???????/    \\????????_???????/     ____ >\n| \\??????????_????????/   \\????|  __  |?????/   \\????|     |\n \\ \\???????/   \\?????|     |???|  ||  |????|     |???|     |",
+        "                                                          ____\n                       __???????????????????????????????/   o  \\\n  _??????????_???????/    \\????????_??????????????????/     ____ >\n | \\???????/   \\????|  __  |?????/   \\????????_??????|     |\n  \\ \\?????|     |???|  ||  |????|     |?????/   \\????|     |",
+    ],
+    masks: &[
+        "\n                                                            W",
+    ],
+    transparency: Transparency::Auto,
+};
+
+pub const OLD_MONSTER_LEFT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "    ____\n  /  o   \\??????????????????????????????????????????__\n< ____     \\???????_?????????????????????_????????/    \\\n      |     |????/   \\????????_????????/   \\?????|  __  |??????_\n      |     |???|     |?????/   \\?????|     |????|  ||  |?????/ |",
+        "    ____\n  /  o   \\?????????__\n< ____     \\?????/    \\???????_?????????????????????_\n      |     |???|  __  |????/   \\????????_????????/   \\???????_\n      |     |???|  ||  |???|     |?????/   \\?????|     |?????/ |",
+        "    ____\n  /  o   \\????????????????????__\n< ____     \\???????_????????/    \\???????_??????????????????????_\n      |     |????/   \\?????|  __  |????/   \\????????_??????????/ |\n      |     |???|     |????|  ||  |???|     |?????/   \\???????/ /",
+        "    ____\n  /  o   \\???????????????????????????????__\n< ____     \\??????????????????_????????/    \\???????_??????????_\n      |     |??????_????????/   \\?????|  __  |????/   \\???????/ |\n      |     |????/   \\?????|     |????|  ||  |???|     |?????/ /",
+    ],
+    masks: &[
+        "\n     W",
+    ],
+    transparency: Transparency::Auto,
+};
+
+pub const BIG_FISH_1_RIGHT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        " ______\n`\"\"-.  `````-----.....__\n     `.  .      .       `-.\n       :     .     .       `.\n ,?????:   .    .          _ :\n: `.???:                  (@) `._\n `. `..'     .     =`-.       .__)\n   ;     .        =  ~  :     .-\"\n .' .'`.   .    .  =.-'  `._ .'\n: .'???:               .   .'\n '???.'  .    .     .   .-'\n   .'____....----''.'=.'\n   \"\"?????????????.'.'\n               ''\"'`",
+    ],
+    masks: &[
+        " 111111\n11111  11111111111111111\n     11  2      2       111\n       1     2     2       11\n 1     1   2    2          1 1\n1 11   1                  1W1 111\n 11 1111     2     1111       1111\n   1     2        1  1  1     111\n 11 1111   2    2  1111  111 11\n1 11   1               2   11\n 1   11  2    2     2   111\n   111111111111111111111\n   11             1111\n               11111",
+    ],
+    transparency: Transparency::Auto,
+};
+
+pub const BIG_FISH_1_LEFT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "                           ______\n          __.....-----'''''  .-\"\"'\n       .-'       .      .  .'\n     .'       .     .     :\n    : _          .    .   :?????,\n _.' (@)                  :???.' :\n(__.       .-'=     .     `..' .'\n \"-.     :  ~  =        .     ;\n   `. _.'  `-.=  .    .   .'`. `.\n     `.   .               :???`. :\n       `-.   .     .    .  `.???`\n          `.=`.``----....____`.\n            `.`.?????????????\"\"\n              '`\"``",
+    ],
+    masks: &[
+        "                           111111\n          11111111111111111  11111\n       111       2      2  11\n     11       2     2     1\n    1 1          2    2   1     1\n 111 1W1                  1   11 1\n1111       1111     2     1111 11\n 111     1  1  1        2     1\n   11 111  1111  2    2   1111 11\n     11   2               1   11 1\n       111   2     2    2  11   1\n          111111111111111111111\n            1111             11\n              11111",
+    ],
+    transparency: Transparency::Auto,
+};
+
+pub const BIG_FISH_2_RIGHT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "                _ _ _\n             .='\\ \\ \\`\"=,\n           .'\\ \\ \\ \\ \\ \\ \\\n\\'=._?????/ \\ \\ \\_\\_\\_\\_\\_\\\n\\'=._'.??/\\ \\,-\"`- _ - _ - '-.\n  \\`=._\\|'.\\/- _ - _ - _ - _- \\\n  ;\"= ._\\=./_ -_ -_ {`\"=_    @ \\\n   ;=\"_-_=- _ -  _ - {\"=_\"-     \\\n   ;_=_--_.,          {_.='   .-/\n  ;.=\"` / ';\\        _.     _.-`\n  /_.='/ \\/ /;._ _ _{.-;`/\"`\n/._=_.'???'/ / / / /{.= /\n/.=' ??????`'./_/_.=`{_/",
+    ],
+    masks: &[
+        "                1 1 1\n             1111 1 11111\n           111 1 1 1 1 1 1\n11111     1 1 1 11111111111\n1111111  11 111112 2 2 2 2 111\n  111111111112 2 2 2 2 2 2 22 1\n  111 1111 12 22 22 11111    W 1\n   11111112 2 2  2 2 111111     1\n   111111111          11111   111\n  11111 11111        11     1111\n  111111 11 1111 1 111111111\n1111111   11 1 1 1 1111 1\n1111       1111111111111",
+    ],
+    transparency: Transparency::Auto,
+};
+
+pub const BIG_FISH_2_LEFT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "            _ _ _\n        ,=\"`/ / /'=.\n       / / / / / / /'.\n      /_/_/_/_/_/ / / \\?????_.='/\n   .-' - _ - _ -`\"-,/ /\\??.'_.='/\n  / -_ - _ - _ - _ -\\/.'|/_.=`/\n / @    _=\"`} _- _- _\\.=/_. =\";\n/     -\"_=\"} - _  - _ -=_-_\"=;\n\\-.   '=._}          ,._--_=_;\n `-._     ._        /;' \\ `\"=.;\n     `\"\\`;-.}_ _ _.;\\ \\/ \\'=._\\\n        \\ =.}\\ \\ \\ \\ \\'???'._=_.\\\n         \\_}`=._\\_\\.'`???????'=.\\",
+    ],
+    masks: &[
+        "            1 1 1\n        11111 1 1111\n       1 1 1 1 1 1 111\n      11111111111 1 1 1     11111\n   111 2 2 2 2 211111 11  1111111\n  1 22 2 2 2 2 2 2 211111111111\n 1 W    11111 22 22 2111111 111\n1     111111 2 2  2 2 21111111\n111   11111          111111111\n 1111     11        111 1 11111\n     111111111 1 1111 11 111111\n        1 1111 1 1 1 11   1111111\n         1111111111111       1111",
+    ],
+    transparency: Transparency::Auto,
+};
+
+pub const SPLAT: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "\n   .\n  ***\n   '",
+        "\n \",*;`\n \"*,**\n *\"'~'",
+        "  , ,\n \" \",\"'\n *\" *'\"\n  \" ; .",
+        "* ' , ' `\n' ` * . '\n ' `' \",'\n* ' \" * .\n\" * ', '",
+    ],
+    masks: &[],
+    transparency: Transparency::Spaces,
+};
+
+pub const CASTLE: GeneratedSprite = GeneratedSprite {
+    frames: &[
+        "               T~~\n               |\n              /^\\\n             /   \\\n _   _   _  /     \\  _   _   _\n[ ]_[ ]_[ ]/ _   _ \\[ ]_[ ]_[ ]\n|_=__-_ =_|_[ ]_[ ]_|_=-___-__|\n | _- =  | =_ = _    |= _=   |\n |= -[]  |- = _ =    |_-=_[] |\n | =_    |= - ___    | =_ =  |\n |=  []- |-  /| |\\   |=_ =[] |\n |- =_   | =| | | |  |- = -  |\n |_______|__|_|_|_|__|_______|",
+    ],
+    masks: &[
+        "                RR\n\n              yyy\n             y   y\n            y     y\n           y       y\n\n\n\n              yyy\n             yy yy\n            y y y y\n            yyyyyyy",
+    ],
+    transparency: Transparency::Opaque,
+};
+
+/// Surface segments, tiled across the width (top row first).
+pub const WATER_LINE_SEGMENTS: [&str; 4] = [
+    "~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~",
+    "^^^^ ^^^  ^^^   ^^^    ^^^^      ",
+    "^^^^      ^^^^     ^^^    ^^     ",
+    "^^      ^^^^      ^^^    ^^^^^^  ",
+];
+
+/// Seaweed stalk segments; the original alternates them up the stalk.
+pub const SEAWEED_SEGMENTS: [&str; 2] = [
+    "(",
+    " )",
+];
+
+/// Columns from the whale's left edge to the spout frames (right-moving, left-moving).
+pub const WHALE_SPOUT_ALIGN: (usize, usize) = (11, 1);
+
+/// The original lists fish as right/left pairs: even entries face right, odd entries
+/// face left, and each uses its partner as the hand-drawn opposite-facing art.
+pub fn get_generated_fish_assets() -> Vec<FishArt> {
+    let mut out = Vec::new();
+    let arts: &[(&'static str, Option<&'static str>)] = &[
+        (FISH_0001, Some(FISH_MASK_0001)),
+        (FISH_0002, Some(FISH_MASK_0002)),
+        (FISH_0003, Some(FISH_MASK_0003)),
+        (FISH_0004, Some(FISH_MASK_0004)),
+        (FISH_0005, Some(FISH_MASK_0005)),
+        (FISH_0006, Some(FISH_MASK_0006)),
+        (FISH_0007, Some(FISH_MASK_0007)),
+        (FISH_0008, Some(FISH_MASK_0008)),
+        (FISH_0009, Some(FISH_MASK_0009)),
+        (FISH_0010, Some(FISH_MASK_0010)),
+        (FISH_0011, Some(FISH_MASK_0011)),
+        (FISH_0012, Some(FISH_MASK_0012)),
+        (FISH_0013, Some(FISH_MASK_0013)),
+        (FISH_0014, Some(FISH_MASK_0014)),
+        (FISH_0015, Some(FISH_MASK_0015)),
+        (FISH_0016, Some(FISH_MASK_0016)),
+        (FISH_0017, Some(FISH_MASK_0017)),
+        (FISH_0018, Some(FISH_MASK_0018)),
+        (FISH_0019, Some(FISH_MASK_0019)),
+        (FISH_0020, Some(FISH_MASK_0020)),
+        (FISH_0021, Some(FISH_MASK_0021)),
+        (FISH_0022, Some(FISH_MASK_0022)),
+        (FISH_0023, Some(FISH_MASK_0023)),
+        (FISH_0024, Some(FISH_MASK_0024)),
+    ];
+    for (i, (art, mask)) in arts.iter().enumerate() {
+        let (facing, partner) = if i % 2 == 0 {
+            (Facing::Right, arts.get(i + 1))
+        } else {
+            (Facing::Left, arts.get(i - 1))
+        };
+        let mut fish = FishArt::new(art).with_facing(facing);
+        if let Some((partner_art, partner_mask)) = partner {
+            fish = fish.with_flipped(partner_art);
+            if let Some(partner_mask) = partner_mask {
+                fish = fish.with_flipped_mask(partner_mask);
+            }
+        }
+        if let Some(mask) = mask {
+            fish = fish.with_mask(mask);
+        }
+        out.push(fish);
+    }
+    out
+}
@@ -0,0 +1,115 @@
+/*!
+Hit-testing: which entity is drawn on a given cell.
+
+Agent Log:
+- Added for hover/click support; pure functions over `AquariumState`, no egui.
+- Uses the same sprite placement as the renderer (`placed_sprites`), so bobbing, facing and
+  transparency match what is on screen; the topmost opaque sprite wins.
+- Seaweed stalks are hit through `seaweed_cells` (so they can be dragged in edit mode); they
+  sit above ships and back decorations and below everything that swims. Water and bubbles are
+  never hit.
+- `describe_entity` builds tooltip text; the asset ID from an `AssetRegistry` is the species.
+*/
+
+use super::asciiquarium::{
+    placed_sprites, seaweed_cells, sprite_covers, AquariumState, EntityRef, FishArt,
+};
+use super::asset_registry::{AssetRegistry, AssetTag};
+
+/// The topmost entity with an opaque glyph on `cell`, if any.
+pub fn entity_at(
+    state: &AquariumState,
+    assets: &[FishArt],
+    cell: (usize, usize),
+) -> Option<EntityRef> {
+    if cell.0 >= state.size.0 || cell.1 >= state.size.1 {
+        return None;
+    }
+    let sprites = placed_sprites(state, assets);
+    let behind_seaweed = |entity: EntityRef| match entity {
+        EntityRef::Ship(_) => true,
+        EntityRef::Decoration(i) => state.env.decorations.get(i).is_some_and(|d| !d.in_front()),
+        _ => false,
+    };
+    let hit = |swimmer: bool| {
+        sprites
+            .iter()
+            .rev()
+            .filter(|s| swimmer != behind_seaweed(s.entity))
+            .find(|s| sprite_covers(s, cell))
+            .map(|s| s.entity)
+    };
+    let on_stalk = || {
+        let at = (cell.0 as isize, cell.1 as isize);
+        (0..state.env.seaweed.len()).rev().find_map(|i| {
+            seaweed_cells(state, i, &state.env.seaweed[i])
+                .iter()
+                .any(|&(x, y, _)| (x, y) == at)
+                .then_some(EntityRef::Seaweed(i))
+        })
+    };
+    hit(true).or_else(on_stalk).or_else(|| hit(false))
+}
+
+/// Short human-readable label, e.g. `"Fish #3 (classic/fish_0007, small)"` or `"Shark"`.
+pub fn describe_entity(
+    state: &AquariumState,
+    registry: Option<&AssetRegistry>,
+    entity: EntityRef,
+) -> String {
+    match entity {
+        EntityRef::Fish(i) => {
+            let Some(fish) = state.fishes.get(i) else {
+                return format!("Fish #{}", i);
+            };
+            let Some(reg) = registry else {
+                return format!("Fish #{}", i);
+            };
+            let species = reg.id_of(fish.fish_art_index).unwrap_or("unknown");
+            let size = if reg.tags_of(fish.fish_art_index).contains(&AssetTag::Small) {
+                "small"
+            } else {
+                "large"
+            };
+            format!("Fish #{} ({}, {})", i, species, size)
+        }
+        EntityRef::Ship(_) => "Ship".to_string(),
+        EntityRef::Shark(_) => "Shark".to_string(),
+        EntityRef::Whale(_) => "Whale".to_string(),
+        EntityRef::Seaweed(_) => "Seaweed".to_string(),
+        EntityRef::Decoration(i) => state
+            .env
+            .decorations
+            .get(i)
+            .map_or("Decoration", |d| d.kind.name())
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::asciiquarium::FishInstance;
+
+    #[test]
+    fn topmost_opaque_sprite_wins() {
+        let assets = vec![FishArt::new("#####"), FishArt::new("  <>")];
+        let mut state = AquariumState {
+            size: (10, 3),
+            ..Default::default()
+        };
+        state.env.decorations.clear();
+        for idx in 0..2 {
+            state.fishes.push(FishInstance {
+                fish_art_index: idx,
+                position: (0.0, 1.0),
+                velocity: (0.0, 0.0),
+            });
+        }
+        // Leading blanks of the front fish are transparent: the back fish is hit.
+        assert_eq!(entity_at(&state, &assets, (1, 1)), Some(EntityRef::Fish(0)));
+        assert_eq!(entity_at(&state, &assets, (2, 1)), Some(EntityRef::Fish(1)));
+        assert_eq!(entity_at(&state, &assets, (7, 1)), None);
+        assert_eq!(entity_at(&state, &assets, (2, 0)), None);
+    }
+}
@@ -0,0 +1,292 @@
+/*!
+HTML and SVG export of a frame, plus an animated HTML page with a tiny player.
+
+Agent Log:
+- Added for embedding snapshots in status pages. Colouring is the widget's: cells are split
+  into the painter's coloured runs (`row_spans`, so `shade_color` with palette, layer and
+  day/night shade), and the theme background fills the frame.
+- HTML is a `<pre>` with one `<span>` per run; the gaps between runs are spaces, so the text
+  copies out as the plain tank.
+- SVG has one monospace `<text>` per run, placed at its cell. `textLength` pins every run to
+  its cell width so a font with slightly different advances cannot shift a row.
+- The animated page simulates a copy of the state and embeds the `<pre>` contents of each
+  frame, with its background (day/night shades it), as a JSON array; a few lines of JS swap
+  them on a timer (click to pause). No external scripts or styles.
+*/
+
+use std::fmt::Write;
+
+use egui::Color32;
+
+use super::asciicast::json_string;
+use super::asciiquarium::{
+    render_aquarium_to_grid, update_aquarium, AquariumState, AsciiquariumTheme, FishArt, CLASSIC_DT,
+};
+use super::asciiquarium_painter::row_spans;
+use super::day_night::shade_for;
+use super::text_width::display_width;
+
+/// SVG font size and cell size in pixels (monospace advance is about 0.6 em).
+const SVG_FONT_PX: f32 = 14.0;
+const SVG_CELL_W: f32 = 8.4;
+const SVG_CELL_H: f32 = 17.0;
+/// Background when the theme has none.
+const DEFAULT_BACKGROUND: Color32 = Color32::BLACK;
+
+/// CSS colour for `color` (`#rrggbb`, or `rgba()` when translucent).
+pub(crate) fn css_color(color: Color32) -> String {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    if a == 255 {
+        format!("#{r:02x}{g:02x}{b:02x}")
+    } else {
+        format!("rgba({r},{g},{b},{:.3})", a as f32 / 255.0)
+    }
+}
+
+/// `text` with `&`, `<`, `>` and `"` escaped.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Frame background: the theme's (or `DEFAULT_BACKGROUND`) under the day/night shade.
+pub(crate) fn background(state: &AquariumState, theme: &AsciiquariumTheme) -> Color32 {
+    let fill = theme.background.unwrap_or(DEFAULT_BACKGROUND);
+    shade_for(state).map_or(fill, |s| s.background(fill))
+}
+
+/// Each row of the tank as HTML: coloured spans, with spaces between them.
+pub(crate) fn html_rows(
+    state: &AquariumState,
+    assets: &[FishArt],
+    theme: &AsciiquariumTheme,
+) -> Vec<String> {
+    let grid = render_aquarium_to_grid(state, assets);
+    let shade = shade_for(state);
+    let mut rows = Vec::with_capacity(grid.height);
+    for y in 0..grid.height {
+        let mut row = String::new();
+        let mut col = 0;
+        for span in row_spans(&grid, y, theme, shade.as_ref()) {
+            row.extend(std::iter::repeat(' ').take(span.col.saturating_sub(col)));
+            let _ = write!(
+                row,
+                "<span style=\"color:{}\">{}</span>",
+                css_color(span.color),
+                escape(&span.text)
+            );
+            col = span.col + display_width(&span.text);
+        }
+        rows.push(row);
+    }
+    rows
+}
+
+/// Inner HTML of the `<pre>`: `html_rows` separated by newlines.
+fn pre_contents(state: &AquariumState, assets: &[FishArt], theme: &AsciiquariumTheme) -> String {
+    let mut out = String::new();
+    for row in html_rows(state, assets, theme) {
+        out.push_str(&row);
+        out.push('\n');
+    }
+    out
+}
+
+fn pre_style(state: &AquariumState, theme: &AsciiquariumTheme) -> String {
+    format!(
+        "margin:0;padding:0;background:{};color:{};font-family:monospace;line-height:1.2",
+        css_color(background(state, theme)),
+        css_color(theme.text_color)
+    )
+}
+
+/// The tank as a `<pre>` of coloured `<span>` runs.
+pub fn render_aquarium_to_html(
+    state: &AquariumState,
+    assets: &[FishArt],
+    theme: &AsciiquariumTheme,
+) -> String {
+    format!(
+        "<pre class=\"asciiquarium\" style=\"{}\">{}</pre>\n",
+        pre_style(state, theme),
+        pre_contents(state, assets, theme)
+    )
+}
+
+/// The tank as a standalone SVG of monospace `<text>` runs.
+pub fn render_aquarium_to_svg(
+    state: &AquariumState,
+    assets: &[FishArt],
+    theme: &AsciiquariumTheme,
+) -> String {
+    let grid = render_aquarium_to_grid(state, assets);
+    let shade = shade_for(state);
+    let (w, h) = (
+        grid.width as f32 * SVG_CELL_W,
+        grid.height as f32 * SVG_CELL_H,
+    );
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w:.1}\" height=\"{h:.1}\" \
+         viewBox=\"0 0 {w:.1} {h:.1}\" font-family=\"monospace\" font-size=\"{SVG_FONT_PX}\" \
+         xml:space=\"preserve\">\n<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        css_color(background(state, theme))
+    );
+    for y in 0..grid.height {
+        // Baseline about a fifth of the cell above its bottom.
+        let baseline = (y as f32 + 0.8) * SVG_CELL_H;
+        for span in row_spans(&grid, y, theme, shade.as_ref()) {
+            let _ = writeln!(
+                out,
+                "<text x=\"{:.1}\" y=\"{baseline:.1}\" textLength=\"{:.1}\" lengthAdjust=\"spacingAndGlyphs\" fill=\"{}\">{}</text>",
+                span.col as f32 * SVG_CELL_W,
+                display_width(&span.text) as f32 * SVG_CELL_W,
+                css_color(span.color),
+                escape(&span.text)
+            );
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Simulate a copy of `state` for `frames` frames, `ticks_per_frame` ticks apart, and return
+/// a standalone HTML page that plays them (click the tank to pause).
+pub fn render_aquarium_to_animated_html(
+    state: &AquariumState,
+    assets: &[FishArt],
+    theme: &AsciiquariumTheme,
+    frames: usize,
+    ticks_per_frame: u64,
+) -> String {
+    let mut state = state.clone();
+    let style = pre_style(&state, theme);
+    let mut recorded = Vec::with_capacity(frames.max(1));
+    for _ in 0..frames.max(1) {
+        // The background changes with day/night, so each frame carries its own.
+        recorded.push((
+            css_color(background(&state, theme)),
+            pre_contents(&state, assets, theme),
+        ));
+        for _ in 0..ticks_per_frame.max(1) {
+            update_aquarium(&mut state, assets);
+        }
+    }
+    let delay_ms = (ticks_per_frame.max(1) as f32 * CLASSIC_DT * 1000.0).round();
+    let first = recorded.first().map_or("", |f| f.1.as_str());
+    // `</` would end the script element early.
+    let json: Vec<String> = recorded
+        .iter()
+        .map(|(background, html)| {
+            format!(
+                "{{background:\"{}\",html:{}}}",
+                background,
+                json_string(html).replace("</", "<\\/")
+            )
+        })
+        .collect();
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Asciiquarium</title></head>\n\
+         <body style=\"margin:0;background:#000\">\n\
+         <pre id=\"asciiquarium\" class=\"asciiquarium\" style=\"{style}\">{first}</pre>\n\
+         <script>\n\
+         const frames = [{}];\n\
+         const tank = document.getElementById(\"asciiquarium\");\n\
+         let frame = 0, paused = false;\n\
+         tank.addEventListener(\"click\", () => paused = !paused);\n\
+         setInterval(() => {{\n\
+         \x20 if (paused) return;\n\
+         \x20 frame = (frame + 1) % frames.length;\n\
+         \x20 tank.style.background = frames[frame].background;\n\
+         \x20 tank.innerHTML = frames[frame].html;\n\
+         }}, {delay_ms});\n\
+         </script>\n</body></html>\n",
+        json.join(",\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::asciiquarium::AsciiquariumPalette;
+    use crate::widgets::day_night::{DayClock, DayNight};
+
+    #[test]
+    fn html_and_svg_use_widget_colours() {
+        let mut state = AquariumState {
+            size: (30, 8),
+            ..Default::default()
+        };
+        state.env.decorations.clear();
+        let theme = AsciiquariumTheme {
+            background: Some(Color32::from_rgb(0, 0, 40)),
+            enable_color: true,
+            palette: Some(AsciiquariumPalette {
+                water: Color32::from_rgb(0, 0, 255),
+                water_trail: Color32::BLUE,
+                seaweed: Color32::GREEN,
+                castle: Color32::GRAY,
+                ship: Color32::GRAY,
+                bubble: Color32::WHITE,
+                shark: Color32::GRAY,
+                whale: Color32::GRAY,
+                fish: Color32::YELLOW,
+            }),
+            ..Default::default()
+        };
+        let html = render_aquarium_to_html(&state, &[], &theme);
+        assert!(html.starts_with(
+            "<pre class=\"asciiquarium\" style=\"margin:0;padding:0;background:#000028"
+        ));
+        assert!(html.contains("<span style=\"color:#0000ff\">~~~~~~ "));
+        // Without tags the text is the plain render.
+        let mut plain = String::new();
+        let mut in_tag = false;
+        for c in html.split_once('>').map_or("", |(_, rest)| rest).chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' => in_tag = false,
+                c if !in_tag => plain.push(c),
+                _ => {}
+            }
+        }
+        let text = crate::render_aquarium_to_string(&state, &[]);
+        let trimmed: Vec<&str> = text.trim_end().lines().map(str::trim_end).collect();
+        assert_eq!(plain.trim_end().lines().collect::<Vec<_>>(), trimmed);
+
+        let svg = render_aquarium_to_svg(&state, &[], &theme);
+        assert!(svg.contains("<rect width=\"100%\" height=\"100%\" fill=\"#000028\"/>"));
+        assert!(svg.contains("<text x=\"0.0\" y=\"13.6\" textLength=\"252.0\""));
+
+        let page = render_aquarium_to_animated_html(&state, &[], &theme, 3, 3);
+        let script = page.split_once("<script>").map_or("", |(_, s)| s);
+        assert!(script.contains("<\\/span>") && !script.contains("</span>"));
+        assert!(page.contains("}, 99);"));
+
+        // Under day/night every frame carries its own background.
+        state.env.day_night = Some(DayNight {
+            clock: DayClock::Ticks {
+                ticks_per_day: 24,
+                start_hour: 9.0,
+            },
+            ..Default::default()
+        });
+        let page = render_aquarium_to_animated_html(&state, &[], &theme, 3, 6);
+        let backgrounds: Vec<&str> = page
+            .split("{background:\"")
+            .skip(1)
+            .filter_map(|f| f.split_once('"').map(|(b, _)| b))
+            .collect();
+        assert_eq!(backgrounds.len(), 3);
+        assert_ne!(backgrounds[0], backgrounds[2]);
+        assert!(page.contains(&format!("background:{};", backgrounds[0])));
+    }
+}
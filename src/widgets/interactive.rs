@@ -0,0 +1,291 @@
+/*!
+Interactive Asciiquarium widget: hover, tooltips, click-to-scare and edit mode.
+
+Agent Log:
+- Added because the stateless widgets return a plain `Response` with no idea what is under
+  the pointer.
+- Paints like `AsciiquariumPainter`, then maps the pointer to a cell and the cell to an
+  entity via `hit_test::entity_at`; results come back in `AquariumResponse`.
+- Borrows the state mutably only for click-to-scare (`scare_fish`); everything else is read-only.
+- Tooltip species names come from an optional `AssetRegistry`.
+- Edit mode (`edit_mode: true`) replaces click-to-scare with arranging the tank:
+  - drag a fish, seaweed stalk, decoration or visitor to move it (`editing::move_entity`); the
+    grabbed entity and grab offset live in egui memory, so the widget stays stateless;
+  - right-click an entity, or hover it and press Delete, to remove it;
+  - drop an `AssetDrop` payload (e.g. from `ui.dnd_drag_source`) to add that fish.
+- Hosts usually pause `update_aquarium` while editing and persist the result with `layout`.
+
+Example:
+```text
+let out = AsciiquariumInteractive {
+    state: &mut state,
+    assets: registry.assets(),
+    theme: &theme,
+    registry: Some(&registry),
+    tooltips: true,
+    click_to_scare: true,
+    edit_mode: false,
+}
+.show(ui);
+if let Some(EntityRef::Fish(i)) = out.clicked { /* ... */ }
+```
+*/
+
+use egui;
+
+use super::asciiquarium::{
+    render_aquarium_to_grid, scare_fish, AquariumState, AsciiquariumTheme, EntityRef, FishArt,
+};
+use super::asciiquarium_painter::paint_grid;
+use super::asset_registry::AssetRegistry;
+use super::auto_fit::cell_size;
+use super::day_night::shade_for;
+use super::editing::{add_fish_at, entity_origin, move_entity, remove_entity};
+use super::hit_test::{describe_entity, entity_at};
+
+/// Interactive widget; call `show` instead of `ui.add`.
+pub struct AsciiquariumInteractive<'a> {
+    pub state: &'a mut AquariumState,
+    pub assets: &'a [FishArt],
+    pub theme: &'a AsciiquariumTheme,
+    /// Source of species names for tooltips; None shows plain labels.
+    pub registry: Option<&'a AssetRegistry>,
+    /// Show a tooltip naming the hovered entity.
+    pub tooltips: bool,
+    /// Clicking sends nearby fish darting away (ignored in edit mode).
+    pub click_to_scare: bool,
+    /// Drag, delete and drop entities instead of scaring them.
+    pub edit_mode: bool,
+}
+
+/// Drag-and-drop payload that adds a fish of asset index `.0` where it is dropped in edit mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssetDrop(pub usize);
+
+/// Entity being dragged in edit mode and the cell it was grabbed at, relative to its origin.
+#[derive(Debug, Clone, Copy)]
+struct Grab {
+    entity: EntityRef,
+    offset: (isize, isize),
+}
+
+/// What the pointer did this frame.
+#[derive(Debug)]
+pub struct AquariumResponse {
+    pub response: egui::Response,
+    /// Cell under the pointer, if it is over the tank.
+    pub hovered_cell: Option<(usize, usize)>,
+    /// Entity under the pointer.
+    pub hovered: Option<EntityRef>,
+    /// Entity clicked this frame.
+    pub clicked: Option<EntityRef>,
+    /// Number of fish scared by this frame's click.
+    pub scared: usize,
+    /// Entity dragged to a new position this frame (edit mode).
+    pub moved: Option<EntityRef>,
+    /// Entity removed this frame (edit mode); later indices have shifted down.
+    pub removed: Option<EntityRef>,
+    /// Index of the fish added by a palette drop this frame (edit mode).
+    pub added: Option<usize>,
+}
+
+/// Cell containing `pos` for a grid drawn from `origin` with `cell`-sized cells.
+pub fn cell_at(
+    origin: egui::Pos2,
+    cell: egui::Vec2,
+    size: (usize, usize),
+    pos: egui::Pos2,
+) -> Option<(usize, usize)> {
+    if cell.x <= 0.0 || cell.y <= 0.0 {
+        return None;
+    }
+    let rel = (pos - origin) / cell;
+    if rel.x < 0.0 || rel.y < 0.0 {
+        return None;
+    }
+    let (x, y) = (rel.x.floor() as usize, rel.y.floor() as usize);
+    (x < size.0 && y < size.1).then_some((x, y))
+}
+
+/// Like `cell_at`, but unbounded: positions off the grid give off-grid cells.
+fn signed_cell_at(origin: egui::Pos2, cell: egui::Vec2, pos: egui::Pos2) -> (isize, isize) {
+    let rel = (pos - origin) / cell.max(egui::vec2(1.0, 1.0));
+    (rel.x.floor() as isize, rel.y.floor() as isize)
+}
+
+impl<'a> AsciiquariumInteractive<'a> {
+    pub fn show(mut self, ui: &mut egui::Ui) -> AquariumResponse {
+        let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+        let cell = cell_size(ui, &font_id);
+        let (w, h) = self.state.size;
+        let size = egui::vec2(w as f32 * cell.x, h as f32 * cell.y);
+        let sense = if self.edit_mode {
+            egui::Sense::click_and_drag()
+        } else {
+            egui::Sense::click()
+        };
+        let (rect, response) = ui.allocate_exact_size(size, sense);
+        let edits = if self.edit_mode {
+            self.edit(ui, &response, rect.min, cell)
+        } else {
+            Edits::default()
+        };
+
+        if ui.is_rect_visible(rect) {
+            let grid = render_aquarium_to_grid(self.state, self.assets);
+            let painter = ui.painter_at(rect);
+            paint_grid(
+                &painter,
+                rect,
+                cell,
+                &font_id,
+                &grid,
+                self.theme,
+                shade_for(self.state).as_ref(),
+            );
+            if self.edit_mode && response.dnd_hover_payload::<AssetDrop>().is_some() {
+                let stroke = ui.visuals().selection.stroke;
+                painter.rect_stroke(rect.shrink(1.0), 0.0, stroke);
+            }
+        }
+
+        let hovered_cell = response
+            .hover_pos()
+            .and_then(|pos| cell_at(rect.min, cell, (w, h), pos));
+        let hovered = hovered_cell.and_then(|c| entity_at(self.state, self.assets, c));
+
+        let mut clicked = None;
+        let mut scared = 0;
+        if response.clicked() {
+            let click_cell = response
+                .interact_pointer_pos()
+                .and_then(|pos| cell_at(rect.min, cell, (w, h), pos));
+            if let Some(c) = click_cell {
+                clicked = entity_at(self.state, self.assets, c);
+                if self.click_to_scare && !self.edit_mode {
+                    // Scare from the middle of the clicked cell, not its top-left corner.
+                    let centre = (c.0 as f32 + 0.5, c.1 as f32 + 0.5);
+                    scared = scare_fish(self.state, self.assets, centre);
+                }
+            }
+        }
+
+        let response = match hovered {
+            Some(_) if self.edit_mode && !response.dragged() => {
+                response.on_hover_cursor(egui::CursorIcon::Grab)
+            }
+            _ => response,
+        };
+        let response = match hovered {
+            Some(entity) if self.tooltips => {
+                let text = describe_entity(self.state, self.registry, entity);
+                response.on_hover_text_at_pointer(text)
+            }
+            _ => response,
+        };
+
+        AquariumResponse {
+            response,
+            hovered_cell,
+            hovered,
+            clicked,
+            scared,
+            moved: edits.moved,
+            removed: edits.removed,
+            added: edits.added,
+        }
+    }
+
+    /// Apply this frame's edit-mode drags, deletions and drops.
+    fn edit(
+        &mut self,
+        ui: &egui::Ui,
+        response: &egui::Response,
+        origin: egui::Pos2,
+        cell: egui::Vec2,
+    ) -> Edits {
+        let mut edits = Edits::default();
+        let size = self.state.size;
+        let id = response.id.with("grab");
+
+        if response.drag_started() {
+            let press = ui.input(|i| i.pointer.press_origin());
+            let grab = press
+                .and_then(|pos| cell_at(origin, cell, size, pos))
+                .and_then(|c| {
+                    let entity = entity_at(self.state, self.assets, c)?;
+                    let at = entity_origin(self.state, entity)?;
+                    Some(Grab {
+                        entity,
+                        offset: (c.0 as isize - at.0, c.1 as isize - at.1),
+                    })
+                });
+            ui.data_mut(|d| match grab {
+                Some(grab) => d.insert_temp(id, grab),
+                None => d.remove::<Grab>(id),
+            });
+        }
+        let grab = ui.data(|d| d.get_temp::<Grab>(id));
+        if let (true, Some(grab), Some(pos)) =
+            (response.dragged(), grab, response.interact_pointer_pos())
+        {
+            let (x, y) = signed_cell_at(origin, cell, pos);
+            let to = (x - grab.offset.0, y - grab.offset.1);
+            if move_entity(self.state, self.assets, grab.entity, to) {
+                edits.moved = Some(grab.entity);
+            }
+            ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
+        }
+        if response.drag_stopped() {
+            ui.data_mut(|d| d.remove::<Grab>(id));
+        }
+
+        let pointer_cell = response
+            .hover_pos()
+            .and_then(|pos| cell_at(origin, cell, size, pos));
+        let delete_key = response.hovered()
+            && ui
+                .input(|i| i.key_pressed(egui::Key::Delete) || i.key_pressed(egui::Key::Backspace));
+        if response.secondary_clicked() || delete_key {
+            let target = pointer_cell.and_then(|c| entity_at(self.state, self.assets, c));
+            if let Some(entity) = target {
+                if remove_entity(self.state, entity) {
+                    edits.removed = Some(entity);
+                }
+            }
+        }
+
+        if let Some(drop) = response.dnd_release_payload::<AssetDrop>() {
+            if let Some(c) = pointer_cell {
+                edits.added = add_fish_at(self.state, self.assets, drop.0, c);
+            }
+        }
+        edits
+    }
+}
+
+/// What edit mode changed this frame.
+#[derive(Debug, Default)]
+struct Edits {
+    moved: Option<EntityRef>,
+    removed: Option<EntityRef>,
+    added: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pointer_maps_to_cells() {
+        let origin = egui::pos2(10.0, 20.0);
+        let cell = egui::vec2(7.0, 14.0);
+        assert_eq!(
+            cell_at(origin, cell, (5, 5), egui::pos2(24.5, 34.0)),
+            Some((2, 1))
+        );
+        assert_eq!(cell_at(origin, cell, (5, 5), egui::pos2(9.0, 30.0)), None);
+        assert_eq!(cell_at(origin, cell, (2, 5), egui::pos2(24.5, 34.0)), None);
+        assert_eq!(signed_cell_at(origin, cell, egui::pos2(9.0, 5.0)), (-1, -2));
+    }
+}
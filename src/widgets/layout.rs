@@ -0,0 +1,461 @@
+/*!
+Tank layouts: save and restore an arranged tank as plain text.
+
+Agent Log:
+- Added so kiosk hosts can persist what users arrange in edit mode, without pulling in serde.
+- A layout holds the tank size, the seabed, every decoration, every seaweed stalk, the bubble
+  emitters and the Normal fish (transit fish and visitors are just passing through and are not
+  saved).
+- Fish are stored by registry asset ID rather than index, so layouts survive new assets
+  being registered. Unknown IDs are skipped on load and reported back.
+- Line-based format, one item per line; `#` starts a comment. Fields are separated by any
+  run of whitespace; custom art starts one space after the last field, so it keeps its own
+  leading spaces:
+
+```text
+# asciiquarium layout v1
+size 80 24
+seabed 42 3         (seed, max height; no line = flat floor)
+decoration castle right 1 0 0 0 0   (kind, anchor, x, y, depth, animation period, bubbles)
+decoration custom-auto free 3 2 0 0 0 <*)))\n<*))   (custom art ends the line, `\n` escaped)
+seaweed 12 5 7      (x, height, sway phase)
+emitter decoration 1 90 3 4 0   (anchor, index, period, burst, burst gap, offset)
+emitter cell 30 20 60 1 4 0     (a fixed cell takes x and y instead of an index)
+fish 10 5 6.5 0 classic/fish_0003   (x, y, vx, vy, asset ID)
+```
+*/
+
+use std::fmt;
+
+use super::asciiquarium::{AquariumState, FishBehavior, FishInstance, Seaweed, Transparency};
+use super::asset_registry::AssetRegistry;
+use super::bubbles::{BubbleEmitter, EmitterAnchor};
+use super::decorations::{Decoration, DecorationAnchor, DecorationAnimation, DecorationKind};
+use super::seabed::Seabed;
+
+const HEADER: &str = "# asciiquarium layout v1";
+
+/// A fish in a saved layout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutFish {
+    /// Registry asset ID.
+    pub asset: String,
+    pub position: (f32, f32),
+    pub velocity: (f32, f32),
+}
+
+/// A saved arrangement of the tank.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TankLayout {
+    pub size: (usize, usize),
+    pub seabed: Option<Seabed>,
+    pub decorations: Vec<Decoration>,
+    pub seaweed: Vec<Seaweed>,
+    pub emitters: Vec<BubbleEmitter>,
+    pub fish: Vec<LayoutFish>,
+}
+
+/// A line of layout text that could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutError {
+    /// 1-based line number.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "layout line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+/// Snapshot the arrangement of `state`. Fish whose asset is not in `registry` are left out.
+pub fn capture_layout(state: &AquariumState, registry: &AssetRegistry) -> TankLayout {
+    let fish = state
+        .fishes
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| state.fish_behaviors.get(*i) != Some(&FishBehavior::Transit))
+        .filter_map(|(i, f)| {
+            let cruise = state.fish_cruises.get(i).copied().flatten();
+            Some(LayoutFish {
+                asset: registry.id_of(f.fish_art_index)?.to_string(),
+                position: f.position,
+                // A scared fish is saved at its cruising velocity.
+                velocity: cruise.map_or(f.velocity, |c| c.velocity()),
+            })
+        })
+        .collect();
+    TankLayout {
+        size: state.size,
+        seabed: state.env.seabed.clone(),
+        decorations: state.env.decorations.clone(),
+        seaweed: state.env.seaweed.clone(),
+        emitters: state.env.emitters.clone(),
+        fish,
+    }
+}
+
+/// Replace the arrangement of `state` with `layout`: size, seabed, decorations, seaweed, emitters
+/// and fish. Bubbles are cleared; visitors are kept. Seaweed is no longer managed
+/// automatically afterwards.
+/// Returns the asset IDs that are not in `registry` (those fish are skipped).
+pub fn apply_layout(
+    state: &mut AquariumState,
+    registry: &AssetRegistry,
+    layout: &TankLayout,
+) -> Vec<String> {
+    let mut missing = Vec::new();
+    state.size = layout.size;
+    state.fishes.clear();
+    state.fish_behaviors.clear();
+    state.fish_cruises.clear();
+    state.bubbles.clear();
+    for fish in &layout.fish {
+        let Some(idx) = registry.index_of(&fish.asset) else {
+            missing.push(fish.asset.clone());
+            continue;
+        };
+        state.fishes.push(FishInstance {
+            fish_art_index: idx,
+            position: fish.position,
+            velocity: fish.velocity,
+        });
+        state.fish_behaviors.push(FishBehavior::Normal);
+        state.fish_cruises.push(None);
+    }
+    state.env.seabed = layout.seabed.clone();
+    state.env.decorations = layout.decorations.clone();
+    state.env.seaweed = layout.seaweed.clone();
+    state.env.emitters = layout.emitters.clone();
+    state.env.auto_seaweed = false;
+    // Taken from the restored fish on each resize.
+    state.env.fish_density = None;
+    missing
+}
+
+impl TankLayout {
+    /// The layout in the text format described in the module docs.
+    pub fn to_text(&self) -> String {
+        let mut out = format!("{}\nsize {} {}\n", HEADER, self.size.0, self.size.1);
+        if let Some(seabed) = &self.seabed {
+            out.push_str(&format!("seabed {} {}\n", seabed.seed, seabed.max_height));
+        }
+        for d in &self.decorations {
+            let (period, bubbles) = d.animation.map_or((0, 0), |a| (a.period, a.bubbles));
+            out.push_str(&format!(
+                "decoration {} {} {} {} {} {} {}",
+                kind_key(&d.kind),
+                anchor_key(d.anchor),
+                d.position.0,
+                d.position.1,
+                d.depth,
+                period,
+                bubbles
+            ));
+            if let DecorationKind::Custom { art, .. } = &d.kind {
+                out.push(' ');
+                out.push_str(&art.replace('\\', "\\\\").replace('\n', "\\n"));
+            }
+            out.push('\n');
+        }
+        for s in &self.seaweed {
+            out.push_str(&format!("seaweed {} {} {}\n", s.x, s.height, s.sway_phase));
+        }
+        for e in &self.emitters {
+            let anchor = match e.anchor {
+                EmitterAnchor::Decoration(i) => format!("decoration {}", i),
+                EmitterAnchor::Seaweed(i) => format!("seaweed {}", i),
+                EmitterAnchor::Cell((x, y)) => format!("cell {} {}", x, y),
+            };
+            out.push_str(&format!(
+                "emitter {} {} {} {} {}\n",
+                anchor, e.period, e.burst, e.burst_gap, e.offset
+            ));
+        }
+        for f in &self.fish {
+            out.push_str(&format!(
+                "fish {} {} {} {} {}\n",
+                f.position.0, f.position.1, f.velocity.0, f.velocity.1, f.asset
+            ));
+        }
+        out
+    }
+
+    /// Parse layout text written by `to_text` (blank lines and `#` comments are ignored).
+    pub fn from_text(text: &str) -> Result<TankLayout, LayoutError> {
+        let mut layout = TankLayout::default();
+        for (n, raw) in text.lines().enumerate() {
+            // Only the start is trimmed: trailing spaces can be part of custom art.
+            let line = raw.trim_start().trim_end_matches('\r');
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let err = |message: String| LayoutError {
+                line: n + 1,
+                message,
+            };
+            let mut words = line.split_whitespace();
+            let keyword = words.next().unwrap_or_default();
+            let rest: Vec<&str> = words.collect();
+            match (keyword, rest.as_slice()) {
+                ("size", [w, h]) => layout.size = (parse(w).map_err(err)?, parse(h).map_err(err)?),
+                ("seabed", [seed, max_height]) => {
+                    layout.seabed = Some(
+                        Seabed::new(parse(seed).map_err(err)?)
+                            .with_max_height(parse(max_height).map_err(err)?),
+                    );
+                }
+                ("decoration", [kind, anchor, x, y, depth, period, bubbles, ..]) => {
+                    let custom = rest.len() > 7;
+                    let kind = match parse_kind(kind) {
+                        Some(DecorationKind::Custom { transparency, .. }) if custom => {
+                            // The art is the rest of the line, so it may contain spaces.
+                            let art = rest_of_line(line, 8);
+                            DecorationKind::Custom {
+                                art: unescape(art),
+                                transparency,
+                            }
+                        }
+                        Some(DecorationKind::Custom { .. }) => {
+                            return Err(err("custom decoration without art".to_string()));
+                        }
+                        None => return Err(err(format!("unknown decoration `{}`", kind))),
+                        Some(kind) if !custom => kind,
+                        Some(_) => return Err(err("art is only allowed for custom".to_string())),
+                    };
+                    let Some(anchor) = parse_anchor(anchor) else {
+                        return Err(err(format!("unknown anchor `{}`", anchor)));
+                    };
+                    let period: u64 = parse(period).map_err(err)?;
+                    let bubbles = parse(bubbles).map_err(err)?;
+                    layout.decorations.push(Decoration {
+                        kind,
+                        anchor,
+                        position: (parse(x).map_err(err)?, parse(y).map_err(err)?),
+                        depth: parse(depth).map_err(err)?,
+                        animation: (period > 0).then_some(DecorationAnimation { period, bubbles }),
+                    });
+                }
+                ("seaweed", [x, height, phase]) => layout.seaweed.push(Seaweed {
+                    x: parse(x).map_err(err)?,
+                    height: parse(height).map_err(err)?,
+                    sway_phase: parse(phase).map_err(err)?,
+                }),
+                ("emitter", ["cell", x, y, timing @ ..]) if timing.len() == 4 => {
+                    let cell = (parse(x).map_err(err)?, parse(y).map_err(err)?);
+                    let emitter = parse_emitter(EmitterAnchor::Cell(cell), timing);
+                    layout.emitters.push(emitter.map_err(err)?);
+                }
+                ("emitter", [anchor, index, timing @ ..]) if timing.len() == 4 => {
+                    let index = parse(index).map_err(err)?;
+                    let anchor = match *anchor {
+                        "decoration" => EmitterAnchor::Decoration(index),
+                        "seaweed" => EmitterAnchor::Seaweed(index),
+                        _ => return Err(err(format!("unknown emitter anchor `{}`", anchor))),
+                    };
+                    layout
+                        .emitters
+                        .push(parse_emitter(anchor, timing).map_err(err)?);
+                }
+                ("fish", [x, y, vx, vy, _, ..]) => {
+                    // The asset ID is the rest of the line, so it may contain spaces.
+                    let asset = rest_of_line(line, 5).trim();
+                    layout.fish.push(LayoutFish {
+                        asset: asset.to_string(),
+                        position: (parse(x).map_err(err)?, parse(y).map_err(err)?),
+                        velocity: (parse(vx).map_err(err)?, parse(vy).map_err(err)?),
+                    });
+                }
+                ("size" | "seabed" | "decoration" | "seaweed" | "emitter" | "fish", _) => {
+                    return Err(err(format!("wrong number of values for `{}`", keyword)));
+                }
+                _ => return Err(err(format!("unknown item `{}`", keyword))),
+            }
+        }
+        Ok(layout)
+    }
+}
+
+fn kind_key(kind: &DecorationKind) -> &'static str {
+    match kind {
+        DecorationKind::Castle => "castle",
+        DecorationKind::TreasureChest => "chest",
+        DecorationKind::Rocks => "rocks",
+        DecorationKind::SunkenShip => "sunken-ship",
+        DecorationKind::DiverHelmet => "diver-helmet",
+        DecorationKind::AeratorStone => "aerator",
+        DecorationKind::Custom { transparency, .. } => match transparency {
+            Transparency::Auto => "custom-auto",
+            Transparency::Opaque => "custom-opaque",
+            Transparency::Spaces => "custom-spaces",
+        },
+    }
+}
+
+/// The kind for `key`; custom kinds come back with empty art.
+fn parse_kind(key: &str) -> Option<DecorationKind> {
+    let custom = |transparency| DecorationKind::Custom {
+        art: String::new(),
+        transparency,
+    };
+    Some(match key {
+        "castle" => DecorationKind::Castle,
+        "chest" => DecorationKind::TreasureChest,
+        "rocks" => DecorationKind::Rocks,
+        "sunken-ship" => DecorationKind::SunkenShip,
+        "diver-helmet" => DecorationKind::DiverHelmet,
+        "aerator" => DecorationKind::AeratorStone,
+        "custom-auto" => custom(Transparency::Auto),
+        "custom-opaque" => custom(Transparency::Opaque),
+        "custom-spaces" => custom(Transparency::Spaces),
+        _ => return None,
+    })
+}
+
+fn anchor_key(anchor: DecorationAnchor) -> &'static str {
+    match anchor {
+        DecorationAnchor::Bottom => "bottom",
+        DecorationAnchor::Left => "left",
+        DecorationAnchor::Right => "right",
+        DecorationAnchor::Free => "free",
+    }
+}
+
+fn parse_anchor(key: &str) -> Option<DecorationAnchor> {
+    Some(match key {
+        "bottom" => DecorationAnchor::Bottom,
+        "left" => DecorationAnchor::Left,
+        "right" => DecorationAnchor::Right,
+        "free" => DecorationAnchor::Free,
+        _ => return None,
+    })
+}
+
+/// What follows the first `n` whitespace-separated fields of `line` and the one separator after
+/// them. Fields may be separated by any run of whitespace; after the last one, further spaces
+/// belong to the value (custom art keeps its leading spaces).
+fn rest_of_line(line: &str, n: usize) -> &str {
+    let mut rest = line;
+    for _ in 0..n {
+        rest = rest.trim_start();
+        rest = &rest[rest.find(char::is_whitespace).unwrap_or(rest.len())..];
+    }
+    let mut chars = rest.chars();
+    chars.next();
+    chars.as_str()
+}
+
+/// Undo the `\\` and `\n` escapes used for custom art.
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                out.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                out.push('\\');
+                chars.next();
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// An emitter on `anchor` from its period, burst, burst gap and offset.
+fn parse_emitter(anchor: EmitterAnchor, timing: &[&str]) -> Result<BubbleEmitter, String> {
+    let [period, burst, gap, offset] = timing else {
+        return Err("wrong number of values for `emitter`".to_string());
+    };
+    Ok(BubbleEmitter::new(anchor)
+        .with_period(parse(period)?)
+        .with_burst(parse(burst)?, parse(gap)?)
+        .with_offset(parse(offset)?))
+}
+
+fn parse<T: std::str::FromStr>(word: &str) -> Result<T, String> {
+    word.parse()
+        .map_err(|_| format!("`{}` is not a valid number", word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_round_trips_through_text_and_state() {
+        let registry = AssetRegistry::with_defaults();
+        let mut state = AquariumState {
+            size: (40, 12),
+            ..Default::default()
+        };
+        state.env.decorations.push(
+            Decoration::new(
+                DecorationKind::Custom {
+                    art: "  /\\\n <  >".to_string(),
+                    transparency: Transparency::Opaque,
+                },
+                0,
+            )
+            .with_anchor(DecorationAnchor::Free, (3, 2))
+            .with_depth(200),
+        );
+        state.env.decorations.push(Decoration::treasure_chest(12));
+        state.env.emitters.push(
+            BubbleEmitter::new(EmitterAnchor::Decoration(1))
+                .with_burst(3, 4)
+                .with_offset(7),
+        );
+        state
+            .env
+            .emitters
+            .push(BubbleEmitter::new(EmitterAnchor::Cell((20, 9))));
+        state.env.seabed = Some(Seabed::new(42));
+        state.env.seaweed.push(Seaweed {
+            x: 7,
+            height: 4,
+            sway_phase: 9,
+        });
+        for (i, vx) in [(0usize, 6.5f32), (2, -3.0)] {
+            state.fishes.push(FishInstance {
+                fish_art_index: i,
+                position: (i as f32 * 4.0, 5.5),
+                velocity: (vx, 0.25),
+            });
+        }
+
+        let layout = capture_layout(&state, &registry);
+        let parsed = TankLayout::from_text(&layout.to_text());
+        assert_eq!(parsed.as_ref(), Ok(&layout));
+
+        let mut restored = AquariumState::default();
+        assert!(apply_layout(&mut restored, &registry, &layout).is_empty());
+        assert_eq!(capture_layout(&restored, &registry), layout);
+        assert!(!restored.env.auto_seaweed);
+
+        let bad = TankLayout::from_text("size 10 4\nfish 1 x 0 0 a");
+        assert_eq!(bad.map_err(|e| e.line), Err(2));
+    }
+
+    #[test]
+    fn doubled_spaces_do_not_shift_fields() {
+        let text = "decoration  custom-spaces free\t3 2  0 0 0  ><>\n\
+                    fish 10  5 6.5\t 0  classic/fish_0003\n";
+        let layout = TankLayout::from_text(text).expect("layout parses");
+        assert_eq!(
+            layout.decorations[0].kind,
+            DecorationKind::Custom {
+                art: " ><>".to_string(),
+                transparency: Transparency::Spaces,
+            }
+        );
+        assert_eq!(layout.fish[0].asset, "classic/fish_0003");
+        assert_eq!(layout.fish[0].velocity, (6.5, 0.0));
+    }
+}
@@ -6,19 +6,84 @@ Agent Log:
 - Exposes:
   - `asciiquarium`: core widget, state, update, and render logic.
   - `asciiquarium_assets`: fish ASCII assets and measurement utilities.
-  - `generated_fish_assets`: auto-generated ASCII fish extracted from the original.
+  - `asciiquarium_painter`: widget that paints the cell grid directly (faster for big tanks).
+  - `generated_assets`: auto-generated art for every creature extracted from the original
+    (also reachable as `generated_fish_assets`, its former name). The committed file is
+    used by default; with the `build-assets` feature it is regenerated into `OUT_DIR`.
+  - `asset_registry`: stable asset IDs, tags and seeded queries.
+  - `auto_fit`: grid size from the available egui space and monospace metrics.
+  - `hit_test`: which entity is under a cell, plus tooltip labels.
+  - `interactive`: egui widget with hover, tooltips, click-to-scare and edit mode.
+  - `editing`: move, remove and add entities (used by edit mode).
+  - `layout`: save and restore an arranged tank as text.
+  - `text_width`: grapheme clusters and display widths (wide glyphs take two cells).
+  - `cell_grid`: the cell grid frames are composed into.
+  - `clock`: `SimClock`, fixed ticks for host-fed elapsed time (no `std::time`, so it runs on the web).
+  - `seabed`: optional seeded sand and rock terrain along the bottom.
+  - `decorations`: the castle and other props, with anchors, depth and animation.
+  - `day_night`: optional day/night cycle shading the palette, with glowing fish and moonlight.
+  - `weather`: calm, fair, rainy and stormy surfaces, switchable or scheduled.
+  - `current`: optional flow field drifting bubbles and small fish, bending seaweed.
+  - `bubbles`: bubble growth, popping at the surface and merging.
+  - `wake`: the fading trail behind moving ships.
+  - `food`: sinking pellets that nearby fish swim to and eat.
+  - `bitmap_font`, `png_export` (`image` feature): software rendering to PNG with an embedded
+    5x7 font, for headless previews.
+  - `animation_export` (`image` feature): N simulated ticks as an animated GIF or APNG.
+  - `asciicast`: a simulated run as an asciinema (asciicast v2) recording of ANSI frames.
+  - `html_export`: a frame as HTML (`<pre>` of coloured spans) or SVG, and an animated HTML page.
+  - `stream`: JSON frame/diff and control messages for live remote viewers (`aquarium_server`).
 */
 
+#[cfg(feature = "image")]
+pub mod animation_export;
+pub mod asciicast;
 pub mod asciiquarium;
 pub mod asciiquarium_assets;
-pub mod generated_fish_assets;
+pub mod asciiquarium_painter;
+pub mod asset_registry;
+pub mod auto_fit;
+#[cfg(feature = "image")]
+pub mod bitmap_font;
+pub mod bubbles;
+pub mod cell_grid;
+pub mod clock;
+pub mod current;
+pub mod day_night;
+pub mod decorations;
+pub mod editing;
+pub mod food;
+pub mod hit_test;
+pub mod html_export;
+pub mod interactive;
+pub mod layout;
+#[cfg(feature = "image")]
+pub mod png_export;
+pub mod seabed;
+pub mod stream;
+pub mod text_width;
+pub mod wake;
+pub mod weather;
+/// Every creature's art and colour masks extracted from the original script.
+#[cfg(not(feature = "build-assets"))]
+#[rustfmt::skip]
+pub mod generated_assets;
+
+/// Every creature's art and colour masks, extracted from the original script by `build.rs`.
+#[cfg(feature = "build-assets")]
+pub mod generated_assets {
+    include!(concat!(env!("OUT_DIR"), "/generated_assets.rs"));
+}
 
 pub use asciiquarium_assets::get_fish_assets;
-pub use generated_fish_assets::get_generated_fish_assets;
+pub use asset_registry::{AssetRegistry, AssetTag};
+pub use generated_assets as generated_fish_assets;
+pub use generated_assets::get_generated_fish_assets;
 
 /// Return all fish assets (manual + extracted from original).
+///
+/// Prefer `AssetRegistry::with_defaults()` when fish are addressed by ID; this is the
+/// same list in the same order.
 pub fn get_all_fish_assets() -> Vec<asciiquarium::FishArt> {
-    let mut v = get_fish_assets();
-    v.extend(get_generated_fish_assets());
-    v
+    AssetRegistry::with_defaults().assets().to_vec()
 }
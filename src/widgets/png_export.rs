@@ -0,0 +1,135 @@
+/*!
+PNG export: renders a tank to an image in software, with no GPU or window.
+
+Agent Log:
+- Added behind the `image` feature for docs and chat previews rendered on headless machines.
+- The frame is composed with `render_aquarium_to_grid`, then every cell is drawn with the
+  embedded `bitmap_font` in the colour `shade_color` gives it (palette, layer and day/night
+  shade, as the painter does) over the theme background, or `DEFAULT_BACKGROUND` without one.
+- Cells are `cell_px` pixels wide and twice as tall, like a terminal cell; wide glyphs span
+  two cells.
+- `render_aquarium_to_image` returns the raw `RgbaImage` for callers that encode it themselves.
+*/
+
+use std::io::Cursor;
+
+use egui::Color32;
+use image::{ImageOutputFormat, Rgba, RgbaImage};
+
+use super::asciiquarium::{render_aquarium_to_grid, AquariumState, AsciiquariumTheme, FishArt};
+use super::bitmap_font::{glyph, ink};
+use super::cell_grid::Cell;
+use super::day_night::{shade_color, shade_for};
+use super::text_width::grapheme_width;
+
+/// Background when the theme has none.
+pub const DEFAULT_BACKGROUND: Color32 = Color32::BLACK;
+
+fn rgba(color: Color32) -> Rgba<u8> {
+    Rgba(color.to_array())
+}
+
+/// `color` composited over the opaque `background` (colours are premultiplied).
+fn over(color: Color32, background: Color32) -> Rgba<u8> {
+    let (c, b) = (color.to_array(), background.to_array());
+    let keep = 255 - c[3] as u16;
+    let ch = |i: usize| (c[i] as u16 + (b[i] as u16 * keep + 127) / 255).min(255) as u8;
+    Rgba([ch(0), ch(1), ch(2), 255])
+}
+
+/// Draw the tank into an image of `cell_px` x `2 * cell_px` pixel cells.
+pub fn render_aquarium_to_image(
+    state: &AquariumState,
+    assets: &[FishArt],
+    theme: &AsciiquariumTheme,
+    cell_px: u32,
+) -> RgbaImage {
+    let grid = render_aquarium_to_grid(state, assets);
+    let shade = shade_for(state);
+    let (cw, ch) = (cell_px.max(1) as usize, 2 * cell_px.max(1) as usize);
+    let background = theme.background.unwrap_or(DEFAULT_BACKGROUND);
+    let background = shade.map_or(background, |s| s.background(background));
+    let mut image = RgbaImage::from_pixel(
+        (grid.width * cw) as u32,
+        (grid.height * ch) as u32,
+        rgba(background),
+    );
+    for y in 0..grid.height {
+        for x in 0..grid.width {
+            let Some(Cell::Glyph(g)) = grid.get(x, y) else {
+                continue;
+            };
+            if g == " " {
+                continue;
+            }
+            let color = over(
+                shade_color(&grid, x, y, g, theme, shade.as_ref()),
+                background,
+            );
+            let rows = glyph(g.chars().next().unwrap_or(' '));
+            let span = cw * grapheme_width(g).clamp(1, 2);
+            for py in 0..ch {
+                for px in 0..span {
+                    let ix = (x * cw + px) as u32;
+                    if ix < image.width() && ink(&rows, px, py, span, ch) {
+                        image.put_pixel(ix, (y * ch + py) as u32, color);
+                    }
+                }
+            }
+        }
+    }
+    image
+}
+
+/// The tank as PNG bytes, with `cell_px`-wide cells.
+pub fn render_aquarium_to_png(
+    state: &AquariumState,
+    assets: &[FishArt],
+    theme: &AsciiquariumTheme,
+    cell_px: u32,
+) -> Result<Vec<u8>, image::ImageError> {
+    let image = render_aquarium_to_image(state, assets, theme, cell_px);
+    let mut bytes = Cursor::new(Vec::new());
+    image.write_to(&mut bytes, ImageOutputFormat::Png)?;
+    Ok(bytes.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::asciiquarium::AsciiquariumPalette;
+
+    #[test]
+    fn renders_cells_in_palette_colours() {
+        let mut state = AquariumState {
+            size: (30, 8),
+            ..Default::default()
+        };
+        state.env.decorations.clear();
+        let palette = AsciiquariumPalette {
+            water: Color32::BLUE,
+            water_trail: Color32::BLUE,
+            seaweed: Color32::GREEN,
+            castle: Color32::GRAY,
+            ship: Color32::GRAY,
+            bubble: Color32::WHITE,
+            shark: Color32::GRAY,
+            whale: Color32::GRAY,
+            fish: Color32::YELLOW,
+        };
+        let theme = AsciiquariumTheme {
+            background: Some(Color32::from_rgb(0, 0, 40)),
+            enable_color: true,
+            palette: Some(palette.clone()),
+            ..Default::default()
+        };
+        let image = render_aquarium_to_image(&state, &[], &theme, 6);
+        assert_eq!(image.dimensions(), (180, 96));
+        // Row 0 is all water line; its cells are inked in the water colour.
+        assert!(image.pixels().any(|p| *p == rgba(palette.water)));
+        assert_eq!(*image.get_pixel(0, 95), Rgba([0, 0, 40, 255]));
+
+        let png = render_aquarium_to_png(&state, &[], &theme, 6).unwrap_or_default();
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+}
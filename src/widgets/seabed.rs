@@ -0,0 +1,150 @@
+/*!
+Seabed: an optional, seeded sand and rock profile along the bottom of the tank.
+
+Agent Log:
+- Added so the floor is not a flat empty row; `AquariumEnvironment::seabed` is None by default
+  (the classic look).
+- The profile is value noise: seeded heights at knots every `KNOT_SPACING` columns, eased
+  between, plus a little per-column roughness. Each column is computed on its own, so nothing
+  is cached and widening the tank extends the terrain instead of reshuffling it.
+- Each knot span is sand or rock; rock fills with `#`, sand with a sparse `:,'` texture.
+  Glyphs avoid `.`, `(`/`)` and `~`, which the colour palette reserves for bubbles, seaweed
+  and water.
+- `floor_row` (in `asciiquarium`) is what seaweed, floor-anchored decorations and fish bounce
+  use as the bottom.
+*/
+
+/// Columns between two terrain knots.
+const KNOT_SPACING: isize = 10;
+const SAND: [&str; 6] = [" ", " ", ":", ",", "'", " "];
+
+/// Seeded terrain along the bottom rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seabed {
+    pub seed: u64,
+    /// Tallest the terrain gets, in rows (at least 1).
+    pub max_height: usize,
+}
+
+impl Seabed {
+    /// Terrain up to 3 rows tall.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            max_height: 3,
+        }
+    }
+
+    pub fn with_max_height(mut self, max_height: usize) -> Self {
+        self.max_height = max_height;
+        self
+    }
+
+    fn hash(&self, salt: u64, k: isize) -> u64 {
+        (self.seed ^ salt ^ (k as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407)
+            >> 24
+    }
+
+    fn knot(&self, k: isize) -> f32 {
+        (1 + self.hash(0x51, k) as usize % self.max_height.max(1)) as f32
+    }
+
+    /// Rows of terrain at column `x` (1..=`max_height`).
+    pub fn height_at(&self, x: isize) -> usize {
+        let max = self.max_height.max(1);
+        let k = x.div_euclid(KNOT_SPACING);
+        let t = x.rem_euclid(KNOT_SPACING) as f32 / KNOT_SPACING as f32;
+        let ease = t * t * (3.0 - 2.0 * t);
+        let (a, b) = (self.knot(k), self.knot(k + 1));
+        let base = (a + (b - a) * ease).round() as usize;
+        let bump = usize::from(self.hash(0xB0, x) % 7 == 0);
+        (base + bump).clamp(1, max)
+    }
+
+    /// Whether column `x` is rock rather than sand.
+    pub fn is_rock(&self, x: isize) -> bool {
+        self.hash(0x70, x.div_euclid(KNOT_SPACING)) % 4 == 0
+    }
+
+    /// Glyph at column `x`, `depth` rows below the terrain surface (0 is the surface).
+    pub fn glyph(&self, x: isize, depth: usize) -> &'static str {
+        if depth == 0 {
+            let here = self.height_at(x);
+            return if self.height_at(x + 1) > here {
+                "/"
+            } else if self.height_at(x - 1) > here {
+                "\\"
+            } else {
+                "_"
+            };
+        }
+        if self.is_rock(x) {
+            return "#";
+        }
+        SAND[self.hash(0x5A, x * 31 + depth as isize) as usize % SAND.len()]
+    }
+}
+
+/// First row below open water for an object spanning columns `x0..x0 + width` in a
+/// `size` tank: the top of the highest terrain under it, or the tank height without a seabed.
+pub fn floor_under(
+    seabed: Option<&Seabed>,
+    x0: isize,
+    width: usize,
+    size: (usize, usize),
+) -> usize {
+    let (w, h) = size;
+    let Some(seabed) = seabed else {
+        return h;
+    };
+    let first = x0.max(0);
+    let last = (x0 + width.max(1) as isize).min(w as isize);
+    (first..last)
+        .map(|x| h - seabed.height_at(x).min(h))
+        .min()
+        .unwrap_or(h)
+}
+
+/// Cells of the seabed in a `size` tank, as (x, y, glyph).
+pub fn seabed_cells(seabed: &Seabed, size: (usize, usize)) -> Vec<(isize, isize, &'static str)> {
+    let (w, h) = size;
+    let mut out = Vec::new();
+    for x in 0..w as isize {
+        let height = seabed.height_at(x).min(h);
+        let top = h - height;
+        for y in top..h {
+            out.push((x, y as isize, seabed.glyph(x, y - top)));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terrain_is_seeded_and_bounded() {
+        let seabed = Seabed::new(7).with_max_height(4);
+        let heights: Vec<usize> = (0..60).map(|x| seabed.height_at(x)).collect();
+        assert!(heights.iter().all(|&h| (1..=4).contains(&h)));
+        assert!(heights.iter().any(|&h| h != heights[0]));
+        assert_eq!(
+            heights,
+            (0..60)
+                .map(|x| Seabed::new(7).with_max_height(4).height_at(x))
+                .collect::<Vec<_>>()
+        );
+        assert_ne!(
+            heights,
+            (0..60)
+                .map(|x| Seabed::new(8).with_max_height(4).height_at(x))
+                .collect::<Vec<_>>()
+        );
+
+        let cells = seabed_cells(&seabed, (60, 10));
+        assert_eq!(cells.len(), heights.iter().sum::<usize>());
+    }
+}
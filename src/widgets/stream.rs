@@ -0,0 +1,417 @@
+/*!
+Streaming protocol: one simulation shown live on remote viewers, as JSON frame and diff
+messages, with JSON control messages coming back.
+
+Agent Log:
+- Added for the `aquarium_server` binary (`server` feature), which runs one simulation and
+  pushes these messages to browsers over Server-Sent Events. The protocol itself is plain
+  strings and needs no feature, so it can be driven and tested without sockets.
+- `stream_frame` renders the tank once per tick as HTML rows (`html_export::html_rows`, the
+  widget's colours). Every viewer has its own `FrameStream`, which turns the shared frame into
+  that viewer's next message:
+  - `{"type":"frame","tick":..,"paused":..,"width":..,"height":..,"background":"#..",
+    "color":"#..","rows":["<span ..>..</span>", ..]}` first, and again whenever the size
+    or the background (day/night) changes;
+  - `{"type":"diff","tick":..,"paused":..,"rows":[[y,"row html"], ..]}` with only the rows
+    that changed after that, or nothing when neither the rows nor `paused` changed.
+- Control messages are flat JSON objects with a `type`:
+  - `{"type":"pause"}` and `{"type":"resume"}` stop and restart the clock;
+  - `{"type":"feed","x":40}` drops food (`food::drop_food`), `x` optional;
+  - `{"type":"add_fish","asset":"clownfish","x":10,"y":8}` adds a fish (`add_fish_at`),
+    every field optional; without `asset` one is picked from the registry by tick.
+- The crate has no serde: output uses `asciicast::json_string`, and control messages go
+  through a small parser for flat objects of strings, numbers, booleans and null.
+*/
+
+use std::fmt;
+
+use super::asciicast::json_string;
+use super::asciiquarium::{AquariumState, AsciiquariumTheme, FishArt};
+use super::asset_registry::AssetRegistry;
+use super::editing::add_fish_at;
+use super::food::{drop_food, FEED_PELLETS};
+use super::html_export::{background, css_color, html_rows};
+
+/// One rendered tick, shared by every viewer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamFrame {
+    pub tick: u64,
+    pub paused: bool,
+    /// CSS colours of the background and of uncoloured text.
+    pub background: String,
+    pub color: String,
+    /// `width` is in cells; `rows` holds one HTML string per row.
+    pub width: usize,
+    pub rows: Vec<String>,
+}
+
+/// Render the tank for streaming.
+pub fn stream_frame(
+    state: &AquariumState,
+    assets: &[FishArt],
+    theme: &AsciiquariumTheme,
+    paused: bool,
+) -> StreamFrame {
+    StreamFrame {
+        tick: state.tick,
+        paused,
+        background: css_color(background(state, theme)),
+        color: css_color(theme.text_color),
+        width: state.size.0,
+        rows: html_rows(state, assets, theme),
+    }
+}
+
+/// What one viewer was last sent.
+#[derive(Debug, Clone, Default)]
+pub struct FrameStream {
+    last: Option<StreamFrame>,
+}
+
+impl FrameStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The message bringing this viewer up to `frame`: a full frame first (or after a resize
+    /// or background change), then diffs; None when nothing changed.
+    pub fn next_message(&mut self, frame: &StreamFrame) -> Option<String> {
+        let message = match &self.last {
+            Some(last)
+                if last.width == frame.width
+                    && last.rows.len() == frame.rows.len()
+                    && last.background == frame.background
+                    && last.color == frame.color =>
+            {
+                let changed: Vec<String> = (0..frame.rows.len())
+                    .filter(|&y| last.rows[y] != frame.rows[y])
+                    .map(|y| format!("[{y},{}]", json_string(&frame.rows[y])))
+                    .collect();
+                if changed.is_empty() && last.paused == frame.paused {
+                    return None;
+                }
+                format!(
+                    "{{\"type\":\"diff\",\"tick\":{},\"paused\":{},\"rows\":[{}]}}",
+                    frame.tick,
+                    frame.paused,
+                    changed.join(",")
+                )
+            }
+            _ => {
+                let rows: Vec<String> = frame.rows.iter().map(|r| json_string(r)).collect();
+                format!(
+                    "{{\"type\":\"frame\",\"tick\":{},\"paused\":{},\"width\":{},\"height\":{},\
+                     \"background\":{},\"color\":{},\"rows\":[{}]}}",
+                    frame.tick,
+                    frame.paused,
+                    frame.width,
+                    frame.rows.len(),
+                    json_string(&frame.background),
+                    json_string(&frame.color),
+                    rows.join(",")
+                )
+            }
+        };
+        self.last = Some(frame.clone());
+        Some(message)
+    }
+}
+
+/// A viewer's request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlMessage {
+    Pause,
+    Resume,
+    /// Drop food around column `x` (the middle of the tank without one).
+    Feed {
+        x: Option<usize>,
+    },
+    /// Add a fish of registry ID `asset` (picked by tick without one) centred on `cell`
+    /// (the middle of the tank without one).
+    AddFish {
+        asset: Option<String>,
+        cell: Option<(usize, usize)>,
+    },
+}
+
+/// A control message that could not be parsed or applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControlError {
+    pub message: String,
+}
+
+impl ControlError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ControlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "control message: {}", self.message)
+    }
+}
+
+impl std::error::Error for ControlError {}
+
+/// A value in a flat JSON object.
+#[derive(Debug, Clone, PartialEq)]
+enum Scalar {
+    String(String),
+    Number(f64),
+    Bool(bool),
+    Null,
+}
+
+/// Parse a flat JSON object (no nested objects or arrays) into its fields.
+fn parse_flat_object(text: &str) -> Result<Vec<(String, Scalar)>, ControlError> {
+    let mut chars = text.trim().chars().peekable();
+    let malformed = || ControlError::new("expected a flat JSON object");
+    let skip_ws = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+    };
+    let string = |chars: &mut std::iter::Peekable<std::str::Chars>| -> Option<String> {
+        if chars.next()? != '"' {
+            return None;
+        }
+        let mut out = String::new();
+        loop {
+            match chars.next()? {
+                '"' => return Some(out),
+                '\\' => match chars.next()? {
+                    'n' => out.push('\n'),
+                    't' => out.push('\t'),
+                    'r' => out.push('\r'),
+                    'u' => {
+                        let hex4 = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+                            let hex: String = chars.take(4).collect();
+                            u32::from_str_radix(&hex, 16).ok()
+                        };
+                        let mut code = hex4(chars)?;
+                        // Characters outside the BMP (emoji) come as a UTF-16 surrogate pair.
+                        if (0xD800..0xDC00).contains(&code) {
+                            if (chars.next()?, chars.next()?) != ('\\', 'u') {
+                                return None;
+                            }
+                            let low = hex4(chars).filter(|low| (0xDC00..0xE000).contains(low))?;
+                            code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                        }
+                        out.push(char::from_u32(code)?);
+                    }
+                    c => out.push(c),
+                },
+                c => out.push(c),
+            }
+        }
+    };
+
+    if chars.next() != Some('{') {
+        return Err(malformed());
+    }
+    let mut fields = Vec::new();
+    skip_ws(&mut chars);
+    if chars.peek() == Some(&'}') {
+        chars.next();
+    } else {
+        loop {
+            skip_ws(&mut chars);
+            let key = string(&mut chars).ok_or_else(malformed)?;
+            skip_ws(&mut chars);
+            if chars.next() != Some(':') {
+                return Err(malformed());
+            }
+            skip_ws(&mut chars);
+            let value = match chars.peek() {
+                Some('"') => Scalar::String(string(&mut chars).ok_or_else(malformed)?),
+                Some(_) => {
+                    let mut word = String::new();
+                    while let Some(&c) = chars.peek() {
+                        if c == ',' || c == '}' || c.is_whitespace() {
+                            break;
+                        }
+                        word.push(c);
+                        chars.next();
+                    }
+                    match word.as_str() {
+                        "true" => Scalar::Bool(true),
+                        "false" => Scalar::Bool(false),
+                        "null" => Scalar::Null,
+                        n => Scalar::Number(n.parse().map_err(|_| malformed())?),
+                    }
+                }
+                None => return Err(malformed()),
+            };
+            fields.push((key, value));
+            skip_ws(&mut chars);
+            match chars.next() {
+                Some(',') => continue,
+                Some('}') => break,
+                _ => return Err(malformed()),
+            }
+        }
+    }
+    skip_ws(&mut chars);
+    match chars.next() {
+        None => Ok(fields),
+        Some(_) => Err(malformed()),
+    }
+}
+
+impl ControlMessage {
+    /// Parse a control message (see the module docs for the fields).
+    pub fn from_json(text: &str) -> Result<Self, ControlError> {
+        let fields = parse_flat_object(text)?;
+        let field = |name: &str| fields.iter().find(|(k, _)| k == name).map(|(_, v)| v);
+        let cell = |name: &str| match field(name) {
+            None | Some(Scalar::Null) => Ok(None),
+            Some(Scalar::Number(n)) if *n >= 0.0 => Ok(Some(*n as usize)),
+            Some(_) => Err(ControlError::new(format!(
+                "`{name}` must be a non-negative number"
+            ))),
+        };
+        match field("type") {
+            Some(Scalar::String(t)) => match t.as_str() {
+                "pause" => Ok(Self::Pause),
+                "resume" => Ok(Self::Resume),
+                "feed" => Ok(Self::Feed { x: cell("x")? }),
+                "add_fish" => {
+                    let asset = match field("asset") {
+                        None | Some(Scalar::Null) => None,
+                        Some(Scalar::String(id)) => Some(id.clone()),
+                        Some(_) => return Err(ControlError::new("`asset` must be a string")),
+                    };
+                    let cell = match (cell("x")?, cell("y")?) {
+                        (Some(x), Some(y)) => Some((x, y)),
+                        (None, None) => None,
+                        _ => return Err(ControlError::new("`x` and `y` go together")),
+                    };
+                    Ok(Self::AddFish { asset, cell })
+                }
+                other => Err(ControlError::new(format!("unknown type `{other}`"))),
+            },
+            _ => Err(ControlError::new("missing `type`")),
+        }
+    }
+}
+
+/// Apply `message` to the tank; `paused` is the host's clock switch.
+pub fn apply_control(
+    state: &mut AquariumState,
+    paused: &mut bool,
+    registry: &AssetRegistry,
+    message: &ControlMessage,
+) -> Result<(), ControlError> {
+    let (w, h) = state.size;
+    match message {
+        ControlMessage::Pause => *paused = true,
+        ControlMessage::Resume => *paused = false,
+        ControlMessage::Feed { x } => drop_food(
+            state,
+            x.unwrap_or(w / 2).min(w.saturating_sub(1)),
+            FEED_PELLETS,
+        ),
+        ControlMessage::AddFish { asset, cell } => {
+            let index = match asset {
+                Some(id) => registry
+                    .index_of(id)
+                    .ok_or_else(|| ControlError::new(format!("unknown asset `{id}`")))?,
+                None => registry
+                    .pick(&[], state.tick)
+                    .ok_or_else(|| ControlError::new("no assets to pick from"))?,
+            };
+            add_fish_at(
+                state,
+                registry.assets(),
+                index,
+                cell.unwrap_or((w / 2, h / 2)),
+            )
+            .ok_or_else(|| ControlError::new("asset out of range"))?;
+        }
+    }
+    Ok(())
+}
+
+/// `error` as a reply, `{"type":"error","message":".."}`.
+pub fn error_message(error: &ControlError) -> String {
+    format!(
+        "{{\"type\":\"error\",\"message\":{}}}",
+        json_string(&error.message)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_then_diffs_and_controls_apply() {
+        let registry = AssetRegistry::with_defaults();
+        let mut state = AquariumState {
+            size: (30, 10),
+            ..Default::default()
+        };
+        let theme = AsciiquariumTheme::default();
+        let mut viewer = FrameStream::new();
+        let first = viewer.next_message(&stream_frame(&state, registry.assets(), &theme, false));
+        let first = first.unwrap_or_default();
+        assert!(first.starts_with(
+            "{\"type\":\"frame\",\"tick\":0,\"paused\":false,\"width\":30,\"height\":10,"
+        ));
+        assert_eq!(
+            viewer.next_message(&stream_frame(&state, registry.assets(), &theme, false)),
+            None
+        );
+
+        let mut paused = false;
+        for text in [
+            "{\"type\": \"pause\"}",
+            "{\"type\":\"feed\",\"x\":5}",
+            "{ \"type\":\"add_fish\", \"asset\":null, \"x\":15, \"y\":6 }",
+        ] {
+            let message = ControlMessage::from_json(text);
+            assert!(message.is_ok(), "{text}");
+            if let Ok(message) = message {
+                assert_eq!(
+                    apply_control(&mut state, &mut paused, &registry, &message),
+                    Ok(())
+                );
+            }
+        }
+        assert!(paused);
+        assert_eq!(
+            (state.env.food.len(), state.fishes.len()),
+            (FEED_PELLETS, 1)
+        );
+        let diff = viewer
+            .next_message(&stream_frame(&state, registry.assets(), &theme, paused))
+            .unwrap_or_default();
+        assert!(diff.starts_with("{\"type\":\"diff\",\"tick\":0,\"paused\":true,\"rows\":[["));
+
+        assert_eq!(
+            ControlMessage::from_json("{\"type\":\"add_fish\",\"asset\":\"nope\"}")
+                .and_then(|m| apply_control(&mut state, &mut paused, &registry, &m))
+                .map_err(|e| error_message(&e)),
+            Err("{\"type\":\"error\",\"message\":\"unknown asset `nope`\"}".to_string())
+        );
+        // Emoji arrive as surrogate pairs from `JSON.stringify`; a lone half is malformed.
+        assert_eq!(
+            ControlMessage::from_json(
+                "{\"type\":\"add_fish\",\"asset\":\"\\ud83d\\udc1f \\u00e9\"}"
+            ),
+            Ok(ControlMessage::AddFish {
+                asset: Some("\u{1f41f} \u{e9}".to_string()),
+                cell: None,
+            })
+        );
+        assert!(
+            ControlMessage::from_json("{\"type\":\"add_fish\",\"asset\":\"\\ud83d\"}").is_err()
+        );
+        assert!(ControlMessage::from_json("{\"type\":\"dance\"}").is_err());
+        assert!(ControlMessage::from_json("{\"type\":\"feed\"").is_err());
+        assert!(ControlMessage::from_json("[1]").is_err());
+    }
+}
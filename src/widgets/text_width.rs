@@ -0,0 +1,96 @@
+/*!
+Display-width helpers: split art into grapheme clusters and measure them in terminal cells.
+
+Agent Log:
+- Added so measuring, mirroring and grid placement agree on how many cells a glyph takes.
+- Wide glyphs (CJK, emoji such as 🐟) take two cells; combining marks, variation selectors
+  and zero-width joiners stay attached to the glyph before them.
+- Clustering is a small approximation of UAX #29 (no dependency on a segmentation crate):
+  a base char plus any zero-width chars, with ZWJ joining the next char into the cluster.
+- Widths come from `unicode-width`; a cluster is 1 or 2 cells, never 0.
+*/
+
+use unicode_width::UnicodeWidthChar;
+
+const ZWJ: char = '\u{200D}';
+const EMOJI_PRESENTATION: char = '\u{FE0F}';
+
+/// Split `line` into grapheme clusters (see the module notes for the rules).
+pub fn graphemes(line: &str) -> Graphemes<'_> {
+    Graphemes { rest: line }
+}
+
+/// Iterator returned by `graphemes`.
+#[derive(Debug, Clone)]
+pub struct Graphemes<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Graphemes<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let mut chars = self.rest.char_indices();
+        let (_, first) = chars.next()?;
+        let mut end = first.len_utf8();
+        let mut joined = false;
+        for (i, c) in chars {
+            let attach = joined || c == ZWJ || c.width() == Some(0);
+            if !attach {
+                break;
+            }
+            joined = c == ZWJ;
+            end = i + c.len_utf8();
+        }
+        let (cluster, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(cluster)
+    }
+}
+
+/// Cells taken by one grapheme cluster: 2 for wide glyphs, otherwise 1.
+pub fn grapheme_width(grapheme: &str) -> usize {
+    let mut chars = grapheme.chars();
+    let Some(base) = chars.next() else {
+        return 0;
+    };
+    if base.width().unwrap_or(1) >= 2 || grapheme.contains(EMOJI_PRESENTATION) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Cells taken by a single line.
+pub fn display_width(line: &str) -> usize {
+    graphemes(line).map(grapheme_width).sum()
+}
+
+/// Iterate `line` as (cell offset, grapheme) pairs.
+pub fn cells_of(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut offset = 0usize;
+    graphemes(line).map(move |g| {
+        let at = offset;
+        offset += grapheme_width(g);
+        (at, g)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clusters_keep_marks_and_joiners() {
+        let g: Vec<&str> = graphemes("e\u{301}x👩\u{200D}🔬!").collect();
+        assert_eq!(g, vec!["e\u{301}", "x", "👩\u{200D}🔬", "!"]);
+    }
+
+    #[test]
+    fn widths_count_cells() {
+        assert_eq!(display_width("<º)))><"), 7);
+        assert_eq!(display_width("🐟>"), 3);
+        assert_eq!(display_width("魚"), 2);
+        assert_eq!(display_width("a\u{301}"), 1);
+    }
+}
@@ -0,0 +1,131 @@
+/*!
+Ship wakes: a trail on the waterline behind moving ships that fades over a few ticks.
+
+Agent Log:
+- Added so ships leave a mark on the water. `update_wake` (called from `update_aquarium`)
+  drops a fresh cell behind each ship's stern every tick and ages the rest; `wake_cells`
+  draws them on the Water layer, behind the ships.
+- The wake sits on the lowest water line (`weather::surface_row`) at its column when drawn, so
+  the trail rides the waves instead of the ship's bob.
+- Cells fade through `STAGES` over `WAKE_TICKS`; at the usual ship speed that leaves a trail
+  about five cells long.
+*/
+
+use super::asciiquarium::{ship_sprite, AquariumState};
+use super::asciiquarium_assets::measure_art;
+use super::weather::surface_row;
+
+/// Ticks a wake cell lasts.
+pub const WAKE_TICKS: u8 = 24;
+/// Fading glyphs, freshest first.
+const STAGES: [&str; 4] = ["=", "~", "-", "."];
+
+/// One cell of a wake, on the surface row at column `x`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WakeCell {
+    pub x: isize,
+    /// Ticks since the ship passed.
+    pub age: u8,
+}
+
+impl WakeCell {
+    /// Glyph for the cell's fade stage.
+    pub fn glyph(&self) -> &'static str {
+        let stage = self.age as usize * STAGES.len() / WAKE_TICKS as usize;
+        STAGES[stage.min(STAGES.len() - 1)]
+    }
+}
+
+/// Age the wake and leave a fresh cell behind every moving ship.
+pub fn update_wake(state: &mut AquariumState) {
+    let mut fresh = Vec::new();
+    for (i, ship) in state.env.ships.iter().enumerate() {
+        if ship.vx == 0.0 {
+            continue;
+        }
+        let sprite = ship_sprite(state, i, ship);
+        let (sw, _) = measure_art(sprite.art);
+        let x = if ship.vx > 0.0 {
+            sprite.origin.0 - 1
+        } else {
+            sprite.origin.0 + sw as isize
+        };
+        fresh.push(x);
+    }
+    let wake = &mut state.env.wake;
+    wake.retain_mut(|cell| {
+        cell.age = cell.age.saturating_add(1);
+        cell.age < WAKE_TICKS
+    });
+    for x in fresh {
+        match wake.iter_mut().find(|c| c.x == x) {
+            Some(cell) => cell.age = 0,
+            None => wake.push(WakeCell { x, age: 0 }),
+        }
+    }
+}
+
+/// Cells of the wake, on the grid or not, following the waves as they are drawn this tick.
+pub fn wake_cells(state: &AquariumState) -> Vec<(isize, isize, &'static str)> {
+    let (kind, phase) = (state.env.weather.kind, state.env.water_phase);
+    state
+        .env
+        .wake
+        .iter()
+        .map(|c| {
+            let y = surface_row(kind, phase, c.x.max(0) as usize);
+            (c.x, y as isize, c.glyph())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::asciiquarium::{render_aquarium_to_grid, Ship};
+    use crate::widgets::weather::WeatherKind;
+
+    #[test]
+    fn wake_trails_behind_and_fades() {
+        let mut state = AquariumState {
+            size: (80, 14),
+            ..Default::default()
+        };
+        state.env.weather.kind = WeatherKind::Calm;
+        state.env.ships.push(Ship {
+            x: 20.0,
+            y: 1,
+            vx: 6.0,
+        });
+        for _ in 0..40 {
+            update_wake(&mut state);
+            state.env.ships[0].x += 0.2;
+            state.tick += 1;
+        }
+        let cells = wake_cells(&state);
+        // Freshest right behind the stern, fading further back; nothing older than the fade.
+        assert!(cells.contains(&(26, 3, "=")));
+        assert!(cells
+            .iter()
+            .all(|&(x, y, _)| y == 3 && (21..27).contains(&x)));
+        assert!(cells.iter().any(|&(_, _, g)| g == "."));
+
+        // In waves the trail rides the lowest water line, dipping with it, and is drawn there.
+        state.env.weather.kind = WeatherKind::Fair;
+        let text = render_aquarium_to_grid(&state, &[]).to_text();
+        let rows: Vec<Vec<char>> = text.lines().map(|l| l.chars().collect()).collect();
+        let cells = wake_cells(&state);
+        assert!(cells.iter().any(|&(_, y, _)| y > 3));
+        for (x, y, g) in cells {
+            let surface = surface_row(WeatherKind::Fair, state.env.water_phase, x as usize);
+            assert_eq!(y, surface as isize);
+            assert_eq!(rows[y as usize][x as usize].to_string(), g);
+        }
+
+        state.env.ships.clear();
+        for _ in 0..WAKE_TICKS {
+            update_wake(&mut state);
+        }
+        assert!(state.env.wake.is_empty());
+    }
+}
@@ -0,0 +1,312 @@
+/*!
+Weather: calm, fair, rainy and stormy surfaces.
+
+Agent Log:
+- Added so the waterline is not always the same triangular wave; `AquariumEnvironment::weather`
+  defaults to `Fair`, which is the classic look.
+- The host switches weather by setting `Weather::kind`, or gives a `schedule` of spells that
+  repeats; `update_weather` (called from `update_aquarium`) keeps `kind` in step with it.
+- `wave_offset` is the one place that says how far the surface dips at a column; the renderer
+  draws the water lines from it. `surface_row` is the lowest of the four water lines, where the
+  original's waterline meets the water: bubbles pop, rain splashes, wakes trail and food drops
+  below it.
+- Calm water is flat and still (glassy); storms use a shorter, taller wave with seeded chop,
+  and ships rock harder (`ship_bob`).
+- Rain and storms drop raindrops from the top row; each falls a row per tick through the wave
+  band, drawn over it, and splashes on the lowest water line at its column (`surface_row`) for
+  `SPLASH_TICKS`. The tank has no sky above the surface, so the band is where a drop can be
+  seen falling. Storm rain slants with the wind. Drops that are already falling finish when the
+  weather clears.
+*/
+
+use super::asciiquarium::AquariumState;
+use super::generated_assets::WATER_LINE_SEGMENTS;
+
+/// Ticks a splash stays on the surface.
+const SPLASH_TICKS: u8 = 2;
+/// Columns per new raindrop each tick (rain, storm).
+const RAIN_SPACING: usize = 40;
+const STORM_SPACING: usize = 12;
+
+/// Surface conditions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WeatherKind {
+    /// Flat, still water.
+    Calm,
+    /// The classic gentle wave.
+    #[default]
+    Fair,
+    /// Fair waves with raindrops.
+    Rain,
+    /// Tall, choppy waves, heavy slanted rain and hard-rocking ships.
+    Storm,
+}
+
+impl WeatherKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WeatherKind::Calm => "Calm",
+            WeatherKind::Fair => "Fair",
+            WeatherKind::Rain => "Rain",
+            WeatherKind::Storm => "Storm",
+        }
+    }
+
+    fn rain_spacing(&self) -> Option<usize> {
+        match self {
+            WeatherKind::Rain => Some(RAIN_SPACING),
+            WeatherKind::Storm => Some(STORM_SPACING),
+            _ => None,
+        }
+    }
+}
+
+/// `kind` for `ticks` ticks, as part of a schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeatherSpell {
+    pub kind: WeatherKind,
+    pub ticks: u64,
+}
+
+/// A falling raindrop, or its splash once it reaches the surface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Raindrop {
+    pub x: isize,
+    pub y: isize,
+    /// Columns moved per row fallen (wind).
+    pub drift: isize,
+    /// Ticks of splash left; 0 while still falling.
+    pub splash: u8,
+}
+
+/// Weather state.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Weather {
+    /// Current conditions.
+    pub kind: WeatherKind,
+    /// Spells repeated in order; empty leaves `kind` to the host.
+    pub schedule: Vec<WeatherSpell>,
+    pub raindrops: Vec<Raindrop>,
+}
+
+impl Weather {
+    pub fn new(kind: WeatherKind) -> Self {
+        Self {
+            kind,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_schedule(mut self, schedule: Vec<WeatherSpell>) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    /// Weather the schedule gives at `tick`, if there is one.
+    pub fn scheduled(&self, tick: u64) -> Option<WeatherKind> {
+        let total: u64 = self.schedule.iter().map(|s| s.ticks).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut t = tick % total;
+        for spell in &self.schedule {
+            if t < spell.ticks {
+                return Some(spell.kind);
+            }
+            t -= spell.ticks;
+        }
+        None
+    }
+}
+
+/// Rows the surface dips at column `x` (0 is the top row).
+pub fn wave_offset(kind: WeatherKind, water_phase: u8, x: usize) -> usize {
+    let phase = water_phase as usize;
+    match kind {
+        WeatherKind::Calm => 0,
+        // Triangular wave over columns with phase: 0 -> 1 -> 2 -> 1 repeating.
+        WeatherKind::Fair | WeatherKind::Rain => match (phase + x) % 24 {
+            0..=5 => 0,
+            6..=11 | 18..=23 => 1,
+            _ => 2,
+        },
+        WeatherKind::Storm => {
+            let t = (phase * 2 + x) % 12;
+            let swell = t.abs_diff(6) / 3;
+            let chop = (lcg((x as u64) << 8 ^ (phase as u64 / 2)) >> 33) as usize % 2;
+            swell + chop
+        }
+    }
+}
+
+/// Row of the lowest water line at column `x`, dipped by the wave.
+pub fn surface_row(kind: WeatherKind, water_phase: u8, x: usize) -> usize {
+    WATER_LINE_SEGMENTS.len() - 1 + wave_offset(kind, water_phase, x)
+}
+
+/// Vertical offset of a ship whose left column is `x0`.
+pub fn ship_bob(kind: WeatherKind, tick: u64, x0: isize) -> isize {
+    match kind {
+        WeatherKind::Calm => 0,
+        WeatherKind::Fair | WeatherKind::Rain => {
+            let phase_u = (tick / 64) % 2;
+            let phase_i = (x0.rem_euclid(2)) as u64;
+            ((phase_u + phase_i) % 2) as isize
+        }
+        WeatherKind::Storm => [0, 1, 2, 1][((tick / 12) % 4) as usize],
+    }
+}
+
+fn lcg(seed: u64) -> u64 {
+    seed.wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407)
+}
+
+/// Follow the schedule, spawn new raindrops and move the existing ones.
+pub fn update_weather(state: &mut AquariumState) {
+    let tick = state.tick;
+    let (w, h) = state.size;
+    let phase = state.env.water_phase;
+    let weather = &mut state.env.weather;
+    if let Some(kind) = weather.scheduled(tick) {
+        weather.kind = kind;
+    }
+    let kind = weather.kind;
+
+    weather.raindrops.retain_mut(|drop| {
+        if drop.splash > 0 {
+            drop.splash -= 1;
+            return drop.splash > 0;
+        }
+        drop.y += 1;
+        drop.x += drop.drift;
+        if drop.x < 0 || drop.x >= w as isize {
+            return false;
+        }
+        let surface = surface_row(kind, phase, drop.x as usize) as isize;
+        if drop.y >= surface {
+            drop.y = surface;
+            drop.splash = SPLASH_TICKS;
+        }
+        drop.y < h as isize
+    });
+
+    let Some(spacing) = kind.rain_spacing() else {
+        return;
+    };
+    let drift = isize::from(kind == WeatherKind::Storm);
+    let mut seed = lcg(tick ^ 0x5241_494E);
+    // About one new drop per `spacing` columns each tick.
+    for _ in 0..(w + spacing - 1) / spacing {
+        seed = lcg(seed);
+        if (seed >> 40) % 2 == 0 {
+            continue;
+        }
+        let x = ((seed >> 16) % w.max(1) as u64) as isize;
+        weather.raindrops.push(Raindrop {
+            x,
+            y: 0,
+            drift,
+            splash: 0,
+        });
+    }
+}
+
+/// Cells of raindrops and splashes.
+pub fn rain_cells(state: &AquariumState) -> Vec<(isize, isize, &'static str)> {
+    state
+        .env
+        .weather
+        .raindrops
+        .iter()
+        .filter(|d| d.y >= 0)
+        .map(|d| {
+            let glyph = match (d.splash, d.drift) {
+                (0, 0) => "'",
+                (0, _) => "\\",
+                _ => ",",
+            };
+            (d.x, d.y, glyph)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::asciiquarium::{render_aquarium_to_string, update_aquarium};
+
+    #[test]
+    fn schedule_and_rain_fall_to_the_surface() {
+        let weather = Weather::new(WeatherKind::Fair).with_schedule(vec![
+            WeatherSpell {
+                kind: WeatherKind::Rain,
+                ticks: 10,
+            },
+            WeatherSpell {
+                kind: WeatherKind::Calm,
+                ticks: 10,
+            },
+        ]);
+        assert_eq!(weather.scheduled(3), Some(WeatherKind::Rain));
+        assert_eq!(weather.scheduled(12), Some(WeatherKind::Calm));
+        assert_eq!(weather.scheduled(21), Some(WeatherKind::Rain));
+        assert!((0..40).all(|x| wave_offset(WeatherKind::Calm, 7, x) == 0));
+        assert!((0..40).any(|x| wave_offset(WeatherKind::Storm, 7, x) >= 2));
+
+        let mut state = AquariumState {
+            size: (80, 20),
+            ..Default::default()
+        };
+        state.env.weather = weather;
+        for _ in 0..10 {
+            update_weather(&mut state);
+            state.tick += 1;
+        }
+        let drops = &state.env.weather.raindrops;
+        assert!(!drops.is_empty());
+        assert!(drops.iter().all(|d| d.y <= 5));
+        assert!(drops.iter().any(|d| d.splash > 0));
+
+        // Calm: no new drops, the last ones splash and go.
+        for _ in 0..10 {
+            update_weather(&mut state);
+            state.tick += 1;
+        }
+        assert!(state.env.weather.raindrops.is_empty());
+    }
+
+    #[test]
+    fn rain_is_seen_falling_and_splashing_on_the_water() {
+        let glyphs_after = |kind: WeatherKind| {
+            let mut state = AquariumState {
+                size: (80, 20),
+                ..Default::default()
+            };
+            state.env.weather = Weather::new(kind);
+            let (mut falling, mut splashes) = (Vec::new(), Vec::new());
+            for _ in 0..30 {
+                update_aquarium(&mut state, &[]);
+                let text = render_aquarium_to_string(&state, &[]);
+                let phase = state.env.water_phase;
+                for (y, line) in text.lines().enumerate() {
+                    for (x, c) in line.chars().enumerate() {
+                        let water_line = surface_row(kind, phase, x);
+                        match c {
+                            '\'' if y < water_line => falling.push(y),
+                            ',' if y == water_line => splashes.push(y),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            (falling, splashes)
+        };
+        let (falling, splashes) = glyphs_after(WeatherKind::Rain);
+        // Drops cross every row of the band above the water line, then splash on it.
+        let lowest_line = WATER_LINE_SEGMENTS.len() - 1;
+        assert!((0..lowest_line).all(|y| falling.contains(&y)));
+        assert!(!splashes.is_empty());
+        assert_eq!(glyphs_after(WeatherKind::Calm), (Vec::new(), Vec::new()));
+    }
+}